@@ -0,0 +1,243 @@
+/// required imports
+use crate::config::validate_password;
+use crate::core::{ConnectionInfo, DRACOONClient, DRACOONClientError};
+use chrono::offset::Utc;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+/// constants for API urls
+const DRACOON_USER_ACCOUNT_URL: &str = "user/account";
+const DRACOON_CUSTOMER_INFO_URL: &str = "user/account/customer";
+const DRACOON_NOTIFICATION_CONFIG_URL: &str = "user/notifications/config";
+const DRACOON_ACCOUNT_PASSWORD_URL: &str = "user/account/password";
+
+/// account of the current user
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserAccount {
+    pub id: u64,
+    pub user_name: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub email: Option<String>,
+    pub language: Option<String>,
+    pub is_locked: Option<bool>,
+    pub is_encryption_enabled: Option<bool>,
+    pub has_manageable_rooms: Option<bool>,
+    pub home_room_id: Option<u64>,
+    pub last_login_successful_at: Option<DateTime<Utc>>,
+    pub user_roles: Option<UserRoles>,
+}
+
+/// roles of a user (e.g. "CONFIG_MANAGER", "USER_MANAGER")
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserRoles {
+    pub items: Vec<UserRole>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserRole {
+    pub id: u64,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// who is connected to which tenant with which capabilities (see whoami) - account and
+/// customer are fetched independently: a failed request does not hide the other one
+#[derive(Debug)]
+pub struct WhoAmI {
+    pub account: Result<UserAccount, DRACOONClientError>,
+    pub customer: Result<CustomerInfo, DRACOONClientError>,
+    /// scopes, auth method and expiry of the connection
+    pub connection: ConnectionInfo,
+}
+
+/// customer (tenant) info of the current user incl. storage limits
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomerInfo {
+    pub id: u64,
+    pub name: String,
+    /// storage quota in bytes (0 or negative = unlimited)
+    pub space_limit: i64,
+    pub space_used: i64,
+    pub accounts_limit: Option<i64>,
+    pub accounts_used: Option<i64>,
+}
+
+impl CustomerInfo {
+    /// remaining storage in bytes (None = unlimited)
+    pub fn space_remaining(&self) -> Option<u64> {
+        match self.space_limit {
+            limit if limit <= 0 => None,
+            limit => Some((limit - self.space_used).max(0) as u64),
+        }
+    }
+}
+
+/// notification setting of the current user for one event group (e.g. shares, uploads)
+/// - the channel ids are the channels (e.g. email) notifications are sent to
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationConfig {
+    pub id: u64,
+    pub event_group_name: String,
+    pub channel_ids: Vec<u64>,
+}
+
+/// notification settings of the current user (GET notification config url)
+#[derive(Debug, Serialize, Deserialize)]
+struct NotificationConfigList {
+    items: Vec<NotificationConfig>,
+}
+
+/// request to update a notification setting (PUT to notification config url) - only
+/// set fields are sent
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateNotificationConfigRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_ids: Option<Vec<u64>>,
+}
+
+impl UpdateNotificationConfigRequest {
+    /// request to disable all notifications of the event group (no channels)
+    pub fn disable() -> Self {
+        UpdateNotificationConfigRequest {
+            channel_ids: Some(Vec::new()),
+        }
+    }
+}
+
+/// request to change the password of the current user (PUT to account password url) -
+/// the passwords are zeroized on drop
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChangePasswordRequest {
+    old_password: Zeroizing<String>,
+    new_password: Zeroizing<String>,
+}
+
+/// account implementation for DRACOON client
+impl DRACOONClient {
+    /// fetches the account of the current user
+    pub async fn get_user_account(&self) -> Result<UserAccount, DRACOONClientError> {
+        let api_url = self.api_url(DRACOON_USER_ACCOUNT_URL);
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// fetches account and customer info concurrently and combines them with the scopes
+    /// and auth method of the connection (e.g. for a connection status command) - returns
+    /// BrokenConnection if not connected or the error of the account if both requests fail
+    pub async fn whoami(&self) -> Result<WhoAmI, DRACOONClientError> {
+        let connection = self
+            .connection_info()
+            .ok_or(DRACOONClientError::BrokenConnection)?;
+
+        let (account, customer) = futures::join!(self.get_user_account(), self.get_customer_info());
+
+        let (account, customer) = match (account, customer) {
+            (Err(err), Err(_)) => return Err(err),
+            results => results,
+        };
+
+        Ok(WhoAmI {
+            account,
+            customer,
+            connection,
+        })
+    }
+
+    /// fetches the customer (tenant) info of the current user
+    pub async fn get_customer_info(&self) -> Result<CustomerInfo, DRACOONClientError> {
+        let api_url = self.api_url(DRACOON_CUSTOMER_INFO_URL);
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// checks an upload size against the remaining storage of the customer - returns
+    /// FileTooLarge if the file cannot be stored
+    pub async fn check_upload_size(&self, size: u64) -> Result<(), DRACOONClientError> {
+        let customer = self.get_customer_info().await?;
+
+        match customer.space_remaining() {
+            Some(remaining) if size > remaining => Err(DRACOONClientError::FileTooLarge),
+            _ => Ok(()),
+        }
+    }
+
+    /// fetches the notification settings of the current user (one per event group)
+    pub async fn get_user_notification_config(
+        &self,
+    ) -> Result<Vec<NotificationConfig>, DRACOONClientError> {
+        let api_url = self.api_url(DRACOON_NOTIFICATION_CONFIG_URL);
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url))
+            .await?;
+
+        let configs: NotificationConfigList = self.parse_response(res).await?;
+
+        Ok(configs.items)
+    }
+
+    /// updates a notification setting of the current user by id - returns the updated
+    /// setting (e.g. UpdateNotificationConfigRequest::disable for quiet service accounts)
+    pub async fn update_user_notification_config(
+        &self,
+        config_id: u64,
+        req: &UpdateNotificationConfigRequest,
+    ) -> Result<NotificationConfig, DRACOONClientError> {
+        let api_url = self.api_url(&format!(
+            "{}/{}",
+            DRACOON_NOTIFICATION_CONFIG_URL, config_id
+        ));
+
+        let res = self
+            .send_authenticated_idempotent(self.put(api_url).json(req))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// changes the password of the current user (no admin rights required) - the new
+    /// password is validated against the login password policy first (InvalidPassword)
+    /// - the passwords are only kept in zeroizing buffers by the client (the serialized
+    ///   request body is owned by the HTTP client)
+    /// - not retried: a repeated request would fail with the already changed password
+    pub async fn change_own_password(
+        &self,
+        current: &str,
+        new: &str,
+    ) -> Result<(), DRACOONClientError> {
+        let req = ChangePasswordRequest {
+            old_password: Zeroizing::new(current.to_string()),
+            new_password: Zeroizing::new(new.to_string()),
+        };
+
+        let policies = self.get_password_policies().await?;
+
+        if let Some(policy) = &policies.login_password_policies {
+            validate_password(&req.new_password, policy)?;
+        }
+
+        let api_url = self.api_url(DRACOON_ACCOUNT_PASSWORD_URL);
+
+        let res = self
+            .send_authenticated(self.put(api_url).json(&req))
+            .await?;
+
+        self.parse_empty_response(res).await
+    }
+}
@@ -0,0 +1,226 @@
+/// required imports
+use crate::core::{self, ConnectionInfo, DRACOONClientError, OAuth2ConnectionType, Paged};
+use crate::nodes::Node;
+use crate::uploads::UploadOptions;
+use futures::StreamExt;
+use std::future::Future;
+use std::io::{Read, Write};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio::runtime::{Builder, Handle, Runtime};
+
+/// blocking DRACOON client - drives the async client on an internal (current thread)
+/// runtime, similar to reqwest::blocking
+/// - must not be used from within an async context (e.g. inside a tokio runtime): all
+///   calls return BlockingInAsyncContext instead of panicking there
+pub struct DRACOONClient {
+    inner: core::DRACOONClient,
+    runtime: InternalRuntime,
+}
+
+/// internal runtime - shut down in the background on drop (dropping a runtime within
+/// an async context would panic otherwise)
+struct InternalRuntime(Option<Runtime>);
+
+impl Drop for InternalRuntime {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.0.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+/// adapter to read a blocking reader from the internal runtime
+struct BlockingReader<'a, R>(&'a mut R);
+
+impl<R: Read> AsyncRead for BlockingReader<'_, R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let read = self.0.read(buf.initialize_unfilled())?;
+        buf.advance(read);
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl DRACOONClient {
+    /// creates a blocking DRACOON client with default settings
+    pub fn new(
+        base_url: &str,
+        client_id: String,
+        client_secret: String,
+    ) -> Result<Self, DRACOONClientError> {
+        DRACOONClient::from_async(core::DRACOONClient::new(
+            base_url,
+            client_id,
+            client_secret,
+        )?)
+    }
+
+    /// wraps an async client (e.g. created via DRACOONClient::builder)
+    pub fn from_async(inner: core::DRACOONClient) -> Result<Self, DRACOONClientError> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+
+        Ok(DRACOONClient {
+            inner,
+            runtime: InternalRuntime(Some(runtime)),
+        })
+    }
+
+    /// returns the wrapped async client
+    pub fn inner(&self) -> &core::DRACOONClient {
+        &self.inner
+    }
+
+    /// runs a future to completion on the internal runtime
+    fn block_on<T, F: Future<Output = Result<T, DRACOONClientError>>>(
+        runtime: &InternalRuntime,
+        future: F,
+    ) -> Result<T, DRACOONClientError> {
+        if Handle::try_current().is_ok() {
+            return Err(DRACOONClientError::BlockingInAsyncContext);
+        }
+
+        match &runtime.0 {
+            Some(runtime) => runtime.block_on(future),
+            None => Err(DRACOONClientError::BrokenConnection),
+        }
+    }
+
+    /// main connect method (see async connect)
+    pub fn connect(
+        &mut self,
+        connection_type: OAuth2ConnectionType,
+    ) -> Result<ConnectionInfo, DRACOONClientError> {
+        DRACOONClient::block_on(&self.runtime, self.inner.connect(connection_type))
+    }
+
+    /// completes a login requiring a second factor (see async connect_with_second_factor)
+    pub fn connect_with_second_factor(
+        &mut self,
+        connection_type: OAuth2ConnectionType,
+        otp: &str,
+    ) -> Result<ConnectionInfo, DRACOONClientError> {
+        DRACOONClient::block_on(
+            &self.runtime,
+            self.inner.connect_with_second_factor(connection_type, otp),
+        )
+    }
+
+    /// tests the connection (authenticated ping)
+    pub fn test_connection(&self) -> Result<bool, DRACOONClientError> {
+        DRACOONClient::block_on(&self.runtime, self.inner.test_connection())
+    }
+
+    /// checks if the access token is still valid
+    pub fn check_access_token_validity(&self) -> Result<bool, DRACOONClientError> {
+        self.inner.check_access_token_validity()
+    }
+
+    /// revokes the access token (the refresh token is kept to allow reconnecting)
+    pub fn disconnect(self, revoke_refresh: Option<bool>) -> Result<Self, DRACOONClientError> {
+        let DRACOONClient { inner, runtime } = self;
+        let inner = DRACOONClient::block_on(&runtime, inner.disconnect(revoke_refresh))?;
+
+        Ok(DRACOONClient { inner, runtime })
+    }
+
+    /// revokes the access token (if connected) and consumes the client
+    pub fn close(self) -> Result<(), DRACOONClientError> {
+        let DRACOONClient { inner, runtime } = self;
+
+        DRACOONClient::block_on(&runtime, inner.close())
+    }
+
+    /// fetches a node (room, folder or file) by id
+    pub fn get_node(&self, node_id: u64) -> Result<Node, DRACOONClientError> {
+        DRACOONClient::block_on(&self.runtime, self.inner.get_node(node_id))
+    }
+
+    /// lists the child nodes of a parent (0 = root)
+    pub fn get_nodes(
+        &self,
+        parent_id: u64,
+        offset: Option<u64>,
+        limit: Option<u64>,
+        filter: Option<&str>,
+    ) -> Result<Paged<Node>, DRACOONClientError> {
+        DRACOONClient::block_on(
+            &self.runtime,
+            self.inner.get_nodes(parent_id, offset, limit, filter),
+        )
+    }
+
+    /// creates a folder in the given parent (room or folder)
+    pub fn create_folder(&self, parent_id: u64, name: &str) -> Result<Node, DRACOONClientError> {
+        DRACOONClient::block_on(&self.runtime, self.inner.create_folder(parent_id, name))
+    }
+
+    /// deletes a node (room, folder or file) by id
+    pub fn delete_node(&self, node_id: u64) -> Result<(), DRACOONClientError> {
+        DRACOONClient::block_on(&self.runtime, self.inner.delete_node(node_id))
+    }
+
+    /// streams a file into the given writer - returns the number of bytes written
+    pub fn download<W: Write>(
+        &self,
+        node_id: u64,
+        writer: &mut W,
+    ) -> Result<u64, DRACOONClientError> {
+        DRACOONClient::block_on(&self.runtime, async {
+            let mut stream = Box::pin(self.inner.download_stream(node_id).await?);
+            let mut written: u64 = 0;
+
+            while let Some(chunk) = stream.next().await {
+                let chunk = chunk?;
+                writer.write_all(&chunk)?;
+                written += chunk.len() as u64;
+            }
+
+            writer.flush()?;
+
+            Ok(written)
+        })
+    }
+
+    /// buffers a small file in memory (see async download_to_bytes)
+    pub fn download_to_bytes(&self, node_id: u64) -> Result<Vec<u8>, DRACOONClientError> {
+        DRACOONClient::block_on(&self.runtime, self.inner.download_to_bytes(node_id))
+    }
+
+    /// uploads a file from any reader via upload channel (chunked) and finalizes it
+    pub fn upload<R: Read>(
+        &self,
+        parent_id: u64,
+        name: &str,
+        reader: &mut R,
+        size: Option<u64>,
+        options: UploadOptions,
+    ) -> Result<Node, DRACOONClientError> {
+        let mut reader = BlockingReader(reader);
+
+        DRACOONClient::block_on(
+            &self.runtime,
+            self.inner
+                .upload(parent_id, name, &mut reader, size, options),
+        )
+    }
+
+    /// uploads an in-memory buffer as a file
+    pub fn upload_bytes(
+        &self,
+        parent_id: u64,
+        name: &str,
+        data: &[u8],
+        options: UploadOptions,
+    ) -> Result<Node, DRACOONClientError> {
+        DRACOONClient::block_on(
+            &self.runtime,
+            self.inner.upload_bytes(parent_id, name, data, options),
+        )
+    }
+}
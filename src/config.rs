@@ -0,0 +1,274 @@
+/// required imports
+use crate::core::{DRACOONClient, DRACOONClientError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// constants for API urls
+const DRACOON_PASSWORD_POLICIES_URL: &str = "config/policies/passwords";
+const DRACOON_GENERAL_SETTINGS_URL: &str = "config/info/general";
+const DRACOON_SOFTWARE_VERSION_URL: &str = "public/software/version";
+
+/// minimum API version of the password policies endpoint
+const PASSWORD_POLICIES_MIN_VERSION: ApiVersion = ApiVersion::new(4, 14, 0);
+
+/// character classes of password character rules
+const CHARACTERS_ALPHA: &str = "alpha";
+const CHARACTERS_UPPERCASE: &str = "uppercase";
+const CHARACTERS_LOWERCASE: &str = "lowercase";
+const CHARACTERS_NUMERIC: &str = "numeric";
+const CHARACTERS_SPECIAL: &str = "special";
+const CHARACTERS_ALL: &str = "all";
+
+/// REST API version of a DRACOON server (major.minor.patch) - ordered, e.g. to gate
+/// endpoints that only exist from a certain version on (see requires_version)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ApiVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl ApiVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        ApiVersion {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+/// parses a version string ("4.42.2", missing parts are 0, suffixes like "-beta" and
+/// further parts are ignored) - returns InvalidVersion otherwise
+impl FromStr for ApiVersion {
+    type Err = DRACOONClientError;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        let invalid = || DRACOONClientError::InvalidVersion(version.to_string());
+
+        let core = version.trim().split(['-', '+']).next().unwrap_or_default();
+
+        let mut parts = core.split('.').map(|part| part.parse::<u32>());
+
+        let major = match parts.next() {
+            Some(Ok(major)) => major,
+            _ => return Err(invalid()),
+        };
+        let mut next = || parts.next().unwrap_or(Ok(0)).map_err(|_| invalid());
+
+        Ok(ApiVersion::new(major, next()?, next()?))
+    }
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// software version of the DRACOON server (public version endpoint)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SoftwareVersion {
+    pub rest_api_version: String,
+    pub sds_server_version: Option<String>,
+    pub build_date: Option<String>,
+    pub is_dracoon_cloud: Option<bool>,
+}
+
+impl SoftwareVersion {
+    /// parsed REST API version
+    pub fn api_version(&self) -> Result<ApiVersion, DRACOONClientError> {
+        self.rest_api_version.parse()
+    }
+}
+
+/// general settings of the tenant (feature flags for clients)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneralSettings {
+    pub share_password_sms_enabled: Option<bool>,
+    pub crypto_enabled: Option<bool>,
+    pub media_server_enabled: Option<bool>,
+    pub weak_password_enabled: Option<bool>,
+    pub email_notification_button_enabled: Option<bool>,
+    pub eula_enabled: Option<bool>,
+    pub use_s3_storage: Option<bool>,
+    pub s3_tags_enabled: Option<bool>,
+    pub home_rooms_active: Option<bool>,
+    pub home_room_parent_id: Option<u64>,
+}
+
+/// password policies of the tenant (login, shares, encryption)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordPolicies {
+    pub login_password_policies: Option<PasswordPolicy>,
+    pub shares_password_policies: Option<PasswordPolicy>,
+    pub encryption_password_policies: Option<PasswordPolicy>,
+}
+
+/// single password policy
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordPolicy {
+    pub min_length: Option<usize>,
+    pub character_rules: Option<CharacterRules>,
+    pub reject_dictionary_words: Option<bool>,
+    pub reject_user_info: Option<bool>,
+    pub reject_keyboard_patterns: Option<bool>,
+}
+
+/// required character classes ("alpha", "uppercase", "lowercase", "numeric", "special",
+/// "all" or "none") - the given number of them must be present (0 = all of them)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterRules {
+    pub must_contain_characters: Vec<String>,
+    pub number_of_characteristics_to_enforce: Option<usize>,
+}
+
+/// checks if a password contains a character of the given class
+fn contains_class(password: &str, class: &str) -> bool {
+    match class {
+        CHARACTERS_ALPHA => password.chars().any(char::is_alphabetic),
+        CHARACTERS_UPPERCASE => password.chars().any(char::is_uppercase),
+        CHARACTERS_LOWERCASE => password.chars().any(char::is_lowercase),
+        CHARACTERS_NUMERIC => password.chars().any(|c| c.is_ascii_digit()),
+        CHARACTERS_SPECIAL => password.chars().any(|c| !c.is_alphanumeric()),
+        _ => true,
+    }
+}
+
+/// validates a password against a password policy (length and character rules) - returns
+/// InvalidPassword with a description of the violated rule
+/// - dictionary words, user info and keyboard patterns can only be checked by DRACOON
+pub fn validate_password(
+    password: &str,
+    policy: &PasswordPolicy,
+) -> Result<(), DRACOONClientError> {
+    let invalid = |reason: String| Err(DRACOONClientError::InvalidPassword(reason));
+
+    if let Some(min_length) = policy.min_length {
+        if password.chars().count() < min_length {
+            return invalid(format!(
+                "password must have at least {} characters",
+                min_length
+            ));
+        }
+    }
+
+    let rules = match &policy.character_rules {
+        Some(rules) => rules,
+        None => return Ok(()),
+    };
+
+    let classes: Vec<&str> = match rules
+        .must_contain_characters
+        .iter()
+        .any(|class| class == CHARACTERS_ALL)
+    {
+        true => vec![
+            CHARACTERS_UPPERCASE,
+            CHARACTERS_LOWERCASE,
+            CHARACTERS_NUMERIC,
+            CHARACTERS_SPECIAL,
+        ],
+        false => rules
+            .must_contain_characters
+            .iter()
+            .map(String::as_str)
+            .filter(|class| {
+                matches!(
+                    *class,
+                    CHARACTERS_ALPHA
+                        | CHARACTERS_UPPERCASE
+                        | CHARACTERS_LOWERCASE
+                        | CHARACTERS_NUMERIC
+                        | CHARACTERS_SPECIAL
+                )
+            })
+            .collect(),
+    };
+
+    let required = match rules.number_of_characteristics_to_enforce {
+        Some(required) if required > 0 => required.min(classes.len()),
+        _ => classes.len(),
+    };
+
+    let present = classes
+        .iter()
+        .filter(|class| contains_class(password, class))
+        .count();
+
+    if present < required {
+        return invalid(format!(
+            "password must contain at least {} of: {}",
+            required,
+            classes.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
+/// config implementation for DRACOON client
+impl DRACOONClient {
+    /// fetches the general settings of the tenant (e.g. crypto or SMS share passwords enabled)
+    pub async fn get_general_settings(&self) -> Result<GeneralSettings, DRACOONClientError> {
+        let api_url = self.api_url(DRACOON_GENERAL_SETTINGS_URL);
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// fetches the software version of the server (public, no connection required)
+    pub async fn get_software_version(&self) -> Result<SoftwareVersion, DRACOONClientError> {
+        let api_url = self.api_url(DRACOON_SOFTWARE_VERSION_URL);
+
+        let res = self.send_with_retry(self.http.get(api_url)).await?;
+
+        self.parse_response(res).await
+    }
+
+    /// REST API version of the server - fetched once and kept for the lifetime of the
+    /// client
+    pub async fn api_version(&self) -> Result<ApiVersion, DRACOONClientError> {
+        self.api_version
+            .get_or_try_init(|| async { self.get_software_version().await?.api_version() })
+            .await
+            .copied()
+    }
+
+    /// checks that the server supports at least the given API version - returns
+    /// FeatureUnavailable otherwise (instead of a 404 on a missing endpoint)
+    pub async fn requires_version(&self, min: ApiVersion) -> Result<(), DRACOONClientError> {
+        let available = self.api_version().await?;
+
+        match available >= min {
+            true => Ok(()),
+            false => Err(DRACOONClientError::FeatureUnavailable {
+                required: min,
+                available,
+            }),
+        }
+    }
+
+    /// fetches the password policies of the tenant (login, shares, encryption - servers
+    /// from 4.14)
+    pub async fn get_password_policies(&self) -> Result<PasswordPolicies, DRACOONClientError> {
+        self.requires_version(PASSWORD_POLICIES_MIN_VERSION).await?;
+
+        let api_url = self.api_url(DRACOON_PASSWORD_POLICIES_URL);
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url))
+            .await?;
+
+        self.parse_response(res).await
+    }
+}
@@ -1,32 +1,346 @@
 /// required imports
-use reqwest::header::AUTHORIZATION;
-use reqwest::{Client, Response, Url};
+use crate::config::ApiVersion;
+use crate::endpoints::{Endpoints, DRACOON_API_PREFIX};
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT_LANGUAGE, AUTHORIZATION, CONTENT_TYPE, ETAG, IF_NONE_MATCH, RETRY_AFTER};
+use reqwest::{Certificate, Client, Method, RequestBuilder, Response, Url};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime};
 use chrono::offset::Utc;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::{OnceCell, Semaphore};
+use tokio::task::JoinHandle;
 
 /// constants for grant_type
 const GRANT_TYPE_PASSWORD: &str = "password";
 const GRANT_TYPE_AUTH_CODE: &str = "authorization_code";
 const GRANT_TYPE_REFRESH_TOKEN: &str = "refresh_token";
 const TOKEN_TYPE_HINT_ACCESS: &str = "access_token";
+const SCOPE_ALL: &str = "all";
 
-/// constants for API urls
-const DRACOON_TOKEN_URL: &str = "oauth/token";
-const DRACOON_REDIRECT_URL: &str = "oauth/callback";
-const DRACOON_TOKEN_REVOKE_URL: &str = "oauth/revoke";
+/// constants for API urls (see Endpoints)
 const DRACOON_AUTHENTICATED_PING: &str = "user/ping";
 
+/// response header with the request correlation id (quote it in DRACOON support tickets)
+const DRACOON_REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// rate limit response headers (see rate_limit_status)
+const RATE_LIMIT_LIMIT_HEADER: &str = "x-ratelimit-limit";
+const RATE_LIMIT_REMAINING_HEADER: &str = "x-ratelimit-remaining";
+const RATE_LIMIT_RESET_HEADER: &str = "x-ratelimit-reset";
+
+/// reset values from this size on are unix timestamps, smaller ones seconds until reset
+const RATE_LIMIT_RESET_TIMESTAMP_MIN: i64 = 1_000_000_000;
+
+/// request header for the date format of responses
+const DRACOON_DATE_FORMAT_HEADER: &str = "x-sds-date-format";
+
+/// request header with the tunnelled method (see with_method_override)
+const HTTP_METHOD_OVERRIDE_HEADER: &str = "x-http-method-override";
+
+/// max length of the body included in a GatewayError
+const ERROR_BODY_SNIPPET_LENGTH: usize = 200;
+
+/// placeholder for secrets (tokens, passwords, client secrets) in Debug output
+pub(crate) const MASKED_SECRET: &str = "***";
+
+/// byte order mark some servers / proxies put in front of UTF-8 bodies
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// charsets decoded as Latin-1 (single byte, no invalid sequences)
+const LATIN1_CHARSETS: [&str; 4] = ["iso-8859-1", "latin1", "latin-1", "us-ascii"];
+
+/// DRACOON error codes of 412 (precondition failed) responses with a typed error
+const ERROR_CODE_KEYPAIR_MISSING: i32 = -70020;
+const ERROR_CODES_SECOND_FACTOR_REQUIRED: [i32; 2] = [-10109, -10110];
+
+/// OAuth errors of the token endpoint with a typed error (unauthorized_client is returned
+/// for grant types not allowed for the client as well)
+const OAUTH_ERROR_UNSUPPORTED_GRANT_TYPE: &str = "unsupported_grant_type";
+const OAUTH_ERROR_UNAUTHORIZED_CLIENT: &str = "unauthorized_client";
+const OAUTH_ERROR_INVALID_CLIENT: &str = "invalid_client";
+
 const APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
+/// default limit for authenticated requests in flight at the same time
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 10;
+
+/// default (and max) page size for list endpoints (DRACOON max: 500)
+pub const DEFAULT_PAGE_SIZE: u64 = 500;
+
+/// list responses from this size on (or of unknown size) are deserialized item by item
+/// while they are received instead of being buffered (see ListPage)
+const STREAMED_LIST_MIN_SIZE: u64 = 256 * 1024;
+
+/// default number of retries for idempotent requests on transient failures
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// base delay for retries (doubled on each attempt)
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// max delay between two retries (caps the backoff and Retry-After of the server)
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// reqwest default timeout for idle connections in the pool
+const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// default size cap for downloads buffered in memory (16 MiB)
+pub const DEFAULT_MAX_BUFFERED_DOWNLOAD_SIZE: u64 = 16 * 1024 * 1024;
+
+/// default size cap for API response bodies (JSON and error responses, not downloads)
+pub const DEFAULT_MAX_RESPONSE_BODY_SIZE: u64 = 32 * 1024 * 1024;
+
+/// date format of DRACOON responses (X-Sds-Date-Format header) - only formats which
+/// can be parsed as RFC 3339 are supported
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DateFormat {
+    /// UTC with Z suffix (e.g. 2021-01-01T12:00:00Z)
+    Utc,
+    /// local time with offset (e.g. 2021-01-01T13:00:00+01:00)
+    Offset,
+}
+
+impl DateFormat {
+    fn as_header_value(&self) -> &'static str {
+        match self {
+            DateFormat::Utc => "UTC",
+            DateFormat::Offset => "OFFSET",
+        }
+    }
+}
+
+/// deprecation / warning header returned by DRACOON (e.g. X-Sds-*, Deprecation, Sunset)
+#[derive(Debug, Clone)]
+pub struct ApiWarning {
+    pub url: String,
+    pub header: String,
+    pub value: String,
+}
+
+/// callback for API warnings (see DRACOONClientBuilder::with_warning_callback)
+pub type WarningCallback = Arc<dyn Fn(&ApiWarning) + Send + Sync>;
+
+/// rate limit of the most recent response with rate limit headers (X-RateLimit-Limit,
+/// X-RateLimit-Remaining, X-RateLimit-Reset) - headers missing in that response are None
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitStatus {
+    pub limit: Option<u64>,
+    pub remaining: Option<u64>,
+    /// time the limit resets (reset header as unix timestamp or seconds from received_at)
+    pub reset_at: Option<DateTime<Utc>>,
+    pub received_at: DateTime<Utc>,
+}
+
+/// source of the current time for all expiry logic of the client (token validity,
+/// inactivity window) - see DRACOONClientBuilder::with_clock
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// system time (default clock)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// manually controlled clock for tests (e.g. expire a token without sleeping) - share it
+/// with the client via Arc and advance it from the test
+#[derive(Debug)]
+pub struct MockClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl MockClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        MockClock {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// sets the current time
+    pub fn set(&self, now: DateTime<Utc>) {
+        match self.now.lock() {
+            Ok(mut current) => *current = now,
+            Err(poisoned) => *poisoned.into_inner() = now,
+        }
+    }
+
+    /// moves the current time forward (or backward for negative durations)
+    pub fn advance(&self, duration: chrono::Duration) {
+        self.set(self.now() + duration);
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        match self.now.lock() {
+            Ok(now) => *now,
+            Err(poisoned) => *poisoned.into_inner(),
+        }
+    }
+}
+
+/// masks an optional secret for Debug output (None stays None)
+pub(crate) fn masked(secret: &Option<String>) -> Option<&'static str> {
+    secret.as_ref().map(|_| MASKED_SECRET)
+}
+
+/// checks if a response header is a deprecation / warning header
+fn is_warning_header(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+
+    matches!(name.as_str(), "deprecation" | "sunset" | "warning")
+        || (name.starts_with("x-sds-") && (name.contains("deprecat") || name.contains("warn")))
+}
+
+/// environment variables reqwest reads system proxies from
+const PROXY_ENV_VARS: [&str; 6] = [
+    "HTTP_PROXY",
+    "http_proxy",
+    "HTTPS_PROXY",
+    "https_proxy",
+    "ALL_PROXY",
+    "all_proxy",
+];
+
+/// TLS backend of the HTTP client (reqwest default features)
+const TLS_BACKEND: &str = "native-tls";
+
+/// snapshot of the effective client configuration (diagnostics) - never contains the
+/// client secret or tokens
+/// - for clients sharing a reqwest client (with_shared_http), the HTTP settings of the
+///   shared client are unknown: user agent, timeout and proxy are None then
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientConfig {
+    pub base_url: Url,
+    pub api_prefix: &'static str,
+    pub client_id: String,
+    pub user_agent: Option<String>,
+    pub timeout: Option<Duration>,
+    pub pool_idle_timeout: Option<Duration>,
+    /// system proxy configured via environment (HTTP_PROXY, HTTPS_PROXY, ALL_PROXY)
+    pub proxy: Option<bool>,
+    pub tls_backend: &'static str,
+    pub accept_invalid_certs: bool,
+    pub root_certificates: usize,
+    pub shared_http: bool,
+    pub max_retries: u32,
+    pub max_concurrent_requests: usize,
+    pub default_page_size: u64,
+    pub max_response_body_size: u64,
+    pub method_override: bool,
+}
+
+/// cached response body of a GET request (metadata cache)
+struct CacheEntry {
+    etag: Option<String>,
+    body: String,
+    fetched_at: DateTime<Utc>,
+}
+
+/// in-memory cache for metadata GET requests (see with_metadata_cache) - shared across
+/// clones of the client
+struct MetadataCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl MetadataCache {
+    /// checks if an entry is younger than the TTL at the given time (client clock)
+    fn is_fresh(&self, entry: &CacheEntry, now: DateTime<Utc>) -> bool {
+        chrono::Duration::from_std(self.ttl)
+            .is_ok_and(|ttl| now.signed_duration_since(entry.fetched_at) < ttl)
+    }
+
+    fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+}
+
+/// session shared by all clones of a client - the connection is kept after disconnect
+/// (refresh token for OAuth2ConnectionType::RefreshToken), connected is false then
+#[derive(Default)]
+struct Session {
+    connection: Option<Arc<DRACOONConnection>>,
+    connected: bool,
+}
+
 /// main client struct
+/// - clones are cheap views of one session, not independent logins: they share the HTTP
+///   client, the connection (tokens are refreshed once for all clones), the concurrency
+///   limiter, counters, caches and the dry run flag - hand a clone to each task
+/// - connect, import_connection, disconnect and close change the session of all clones
+#[derive(Clone)]
 pub struct DRACOONClient {
     pub http: Client,
+    endpoints: Endpoints,
+    client_id: String,
+    pub(crate) client_secret: String,
+    session: Arc<RwLock<Session>>,
+    limiter: Arc<Semaphore>,
+    transferred: Arc<TransferCounters>,
+    pub(crate) max_buffered_download_size: u64,
+    method_override: bool,
+    max_response_body_size: u64,
+    debug_mode: bool,
+    default_page_size: u64,
+    max_retries: u32,
+    warning_callback: Option<WarningCallback>,
+    config: ClientConfig,
+    metadata_cache: Option<Arc<MetadataCache>>,
+    refresh_lock: Arc<tokio::sync::Mutex<()>>,
+    dry_run: Arc<AtomicBool>,
+    pub(crate) api_version: Arc<OnceCell<ApiVersion>>,
+    clock: Arc<dyn Clock>,
+    pub(crate) scope: Option<String>,
+    rate_limit: Arc<Mutex<Option<RateLimitStatus>>>,
+}
+
+/// byte counters for file transfers and the number of authenticated requests (shared
+/// across clones of the client)
+#[derive(Debug, Default)]
+struct TransferCounters {
+    uploaded: AtomicU64,
+    downloaded: AtomicU64,
+    requests: AtomicU64,
+}
+
+/// builder for DRACOON client with optional settings
+pub struct DRACOONClientBuilder {
     base_url: Url,
     client_id: String,
     client_secret: String,
-    connection: Option<DRACOONConnection>,
-    connected: bool,
+    max_concurrent_requests: usize,
+    max_buffered_download_size: u64,
+    max_response_body_size: u64,
+    debug_mode: bool,
+    default_page_size: u64,
+    max_retries: u32,
+    language: Option<String>,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Option<Duration>>,
+    timeout: Option<Duration>,
+    root_certificates: Vec<Certificate>,
+    accept_invalid_certs: bool,
+    date_format: Option<DateFormat>,
+    warning_callback: Option<WarningCallback>,
+    shared_http: Option<Client>,
+    metadata_cache_ttl: Option<Duration>,
+    dry_run: bool,
+    method_override: bool,
+    clock: Option<Arc<dyn Clock>>,
+    scopes: Option<Vec<String>>,
 }
 
 /// OAuth2 flow structs (form data for POST to token (revoke) url)
@@ -35,6 +349,12 @@ pub struct OAuth2PasswordFlow {
     pub username: String,
     pub password: String,
     pub grant_type: String,
+    /// one-time password of the second factor (see connect_with_second_factor)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub otp: Option<String>,
+    /// requested scopes (space separated, see with_scopes)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -78,12 +398,61 @@ pub struct OAuth2TokenResponse {
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct DRACOONErrorResponse {
-    code: Option<i32>,
-    message: Option<String>,
-    error: Option<String>,
-    error_description: Option<String>,
-    debug_info: Option<String>,
-    error_code: Option<i32>,
+    pub code: Option<i32>,
+    pub message: Option<String>,
+    pub error: Option<String>,
+    /// OAuth2 errors use snake case (error_description)
+    #[serde(alias = "error_description")]
+    pub error_description: Option<String>,
+    pub debug_info: Option<String>,
+    pub error_code: Option<i32>,
+    /// request id from the response headers (not part of the body)
+    #[serde(skip)]
+    pub request_id: Option<String>,
+}
+
+/// expiration settings (users, nodes, shares) - no date means no expiration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Expiration {
+    #[serde(rename = "enableExpiration")]
+    pub enabled: bool,
+    #[serde(rename = "expireAt", skip_serializing_if = "Option::is_none")]
+    pub date: Option<DateTime<Utc>>,
+}
+
+impl Expiration {
+    pub fn new(date: Option<DateTime<Utc>>) -> Self {
+        Expiration {
+            enabled: date.is_some(),
+            date,
+        }
+    }
+}
+
+/// pagination details of list responses
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Range {
+    pub offset: u64,
+    pub limit: u64,
+    pub total: u64,
+}
+
+/// paged list response (range + items)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Paged<T> {
+    pub range: Range,
+    pub items: Vec<T>,
+}
+
+/// destructive operation skipped in dry run mode (see with_dry_run)
+#[derive(Debug, Clone, PartialEq)]
+pub enum DryRunAction {
+    DeleteNode { node_id: u64 },
+    MoveNodes { target_parent_id: u64, node_ids: Vec<u64> },
+    OverwriteCopy { target_parent_id: u64, node_ids: Vec<u64> },
+    OverwriteUpload { parent_id: u64, name: String },
+    RestoreNodes { deleted_node_ids: Vec<u64>, parent_id: Option<u64> },
+    EmptyRoom { room_id: u64, node_ids: Vec<u64> },
 }
 
 /// main error wrapping other errors (reqwest, JSON parsing)
@@ -92,7 +461,90 @@ pub enum DRACOONClientError {
     RequestFailed(reqwest::Error),
     MissingArguments,
     BrokenConnection,
-    DRACOONErrror(DRACOONErrorResponse),
+    Conflict,
+    Forbidden(Box<DRACOONErrorResponse>),
+    KeypairMissing(Box<DRACOONErrorResponse>),
+    SecondFactorRequired(Box<DRACOONErrorResponse>),
+    UnsupportedGrantType {
+        grant_type: String,
+        hint: String,
+        error: Box<DRACOONErrorResponse>,
+    },
+    InvalidClient(Box<DRACOONErrorResponse>),
+    Cancelled,
+    Timeout,
+    InvalidNodeName(String),
+    InvalidPassword(String),
+    InvalidEmail(String),
+    InvalidRequestBody(String),
+    InvalidProfile(String),
+    ReauthRequired,
+    InvalidRange,
+    SizeMismatch {
+        expected: u64,
+        actual: u64,
+    },
+    FileTooLarge,
+    ResponseTooLarge,
+    DryRun(DryRunAction),
+    InvalidVersion(String),
+    FeatureUnavailable {
+        required: ApiVersion,
+        available: ApiVersion,
+    },
+    InvalidCertificate(reqwest::Error),
+    EncryptionRequired,
+    PreviewUnavailable,
+    CryptoError(String),
+    BlockingInAsyncContext,
+    IoError(std::io::Error),
+    InvalidUrl(url::ParseError),
+    InvalidUtf8(std::string::FromUtf8Error),
+    GatewayError {
+        status: reqwest::StatusCode,
+        body_snippet: String,
+    },
+    DecodeError {
+        body: String,
+        source: serde_json::Error,
+    },
+    DRACOONErrror(Box<DRACOONErrorResponse>),
+}
+
+/// second factor challenge of a login (SecondFactorRequired) - prompt the user for the
+/// one-time password and complete the login with connect_with_second_factor
+#[derive(Debug, Clone)]
+pub struct SecondFactorChallenge {
+    pub message: Option<String>,
+    pub request_id: Option<String>,
+}
+
+impl DRACOONClientError {
+    /// returns the second factor challenge if a login (or an API call) requires a second
+    /// factor
+    pub fn second_factor_challenge(&self) -> Option<SecondFactorChallenge> {
+        match self {
+            DRACOONClientError::SecondFactorRequired(error) => Some(SecondFactorChallenge {
+                message: error.message.clone().or_else(|| error.error_description.clone()),
+                request_id: error.request_id.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// returns the request id of the failed request (if the error was returned by
+    /// DRACOON and the response contained one) - quote it when contacting DRACOON support
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            DRACOONClientError::Forbidden(error)
+            | DRACOONClientError::KeypairMissing(error)
+            | DRACOONClientError::SecondFactorRequired(error)
+            | DRACOONClientError::InvalidClient(error)
+            | DRACOONClientError::UnsupportedGrantType { error, .. }
+            | DRACOONClientError::DRACOONErrror(error) => error.request_id.as_deref(),
+            _ => None,
+        }
+    }
 }
 
 impl From<reqwest::Error> for DRACOONClientError {
@@ -101,241 +553,2106 @@ impl From<reqwest::Error> for DRACOONClientError {
     }
 }
 
+impl From<serde_json::Error> for DRACOONClientError {
+    fn from(error: serde_json::Error) -> Self {
+        DRACOONClientError::DecodeError {
+            body: String::new(),
+            source: error,
+        }
+    }
+}
+
+impl From<std::io::Error> for DRACOONClientError {
+    fn from(error: std::io::Error) -> Self {
+        DRACOONClientError::IoError(error)
+    }
+}
+
+impl From<url::ParseError> for DRACOONClientError {
+    fn from(error: url::ParseError) -> Self {
+        DRACOONClientError::InvalidUrl(error)
+    }
+}
+
 /// struct for storing DRACOON connection details
 #[derive(Debug)]
 pub struct DRACOONConnection {
+    tokens: RwLock<ConnectionTokens>,
+    last_activity: Mutex<DateTime<Utc>>,
+    scopes: Vec<String>,
+    auth_method: Option<AuthMethod>,
+}
+
+/// tokens and expiry of a connection (replaced when the access token is refreshed)
+#[derive(Debug, Clone)]
+struct ConnectionTokens {
     connected_at: DateTime<Utc>,
     access_token: String,
     access_token_validity: i64,
     refresh_token: String,
     refresh_token_validity: i64,
+    absolute_expiry: DateTime<Utc>,
+    inactivity_timeout: i64,
+    token_type: Option<String>,
 }
 
-/// supported OAuth2 flows by client
-pub enum OAuth2ConnectionType {
-    PasswordFlow(String, String),
-    AuthCode(String),
-    RefreshToken,
+impl ConnectionTokens {
+    fn new(token_response: &OAuth2TokenResponse, connected_at: DateTime<Utc>) -> Self {
+        ConnectionTokens {
+            connected_at,
+            access_token: token_response.access_token.to_owned(),
+            refresh_token: token_response.refresh_token.to_owned(),
+            access_token_validity: token_response.expires_in,
+            refresh_token_validity: token_response.expires_in_inactive,
+            absolute_expiry: connected_at + chrono::Duration::seconds(token_response.expires_in),
+            inactivity_timeout: token_response.expires_in_inactive,
+            token_type: token_response.token_type.clone(),
+        }
+    }
 }
 
-/// core connection implementation for DRACOON client
-impl DRACOONClient {
-    /// creates a new DRACOON client instance with given OAuth app credentials and base URL
-    pub fn new(base_url: Url, client_id: String, client_secret: String) -> DRACOONClient {
-        let http = Client::builder()
-            .user_agent(APP_USER_AGENT)
-            .build()
-            .unwrap();
+/// owned summary of an established connection (no secrets) - lifetimes as reported by
+/// the server, e.g. to schedule the next refresh
+#[derive(Debug, Clone)]
+pub struct ConnectionInfo {
+    pub connected_at: DateTime<Utc>,
+    /// absolute expiry of the access token (connected_at + expires_in)
+    pub expires_at: DateTime<Utc>,
+    /// sliding expiry due to inactivity (last activity + expires_in_inactive)
+    pub inactivity_expires_at: DateTime<Utc>,
+    /// lifetime of the access token
+    pub expires_in: Duration,
+    /// inactivity window of the session
+    pub expires_in_inactive: Duration,
+    pub scopes: Vec<String>,
+    /// token type (e.g. "bearer" - None for imported connections)
+    pub token_type: Option<String>,
+    pub auth_method: Option<AuthMethod>,
+}
 
-        DRACOONClient {
-            base_url: base_url,
-            client_id: client_id,
-            client_secret: client_secret,
-            http: http,
-            connected: false,
-            connection: None,
-        }
-    }
+/// persistable connection (tokens and expiry) to resume a session later, e.g. after a
+/// daemon restart - contains the tokens: store it like a password (masked in Debug)
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerializedConnection {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub connected_at: DateTime<Utc>,
+    pub last_activity: DateTime<Utc>,
+    pub expires_in: i64,
+    pub expires_in_inactive: i64,
+    pub scopes: Vec<String>,
+}
 
-    /// generates client credentials for password flow b64enc(client id:client secret)
-    fn client_credentials(&self) -> String {
-        let client_credentials = format!("{}:{}", &self.client_id, &self.client_secret);
+impl std::fmt::Debug for SerializedConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SerializedConnection")
+            .field("access_token", &MASKED_SECRET)
+            .field("refresh_token", &MASKED_SECRET)
+            .field("connected_at", &self.connected_at)
+            .field("last_activity", &self.last_activity)
+            .field("expires_in", &self.expires_in)
+            .field("expires_in_inactive", &self.expires_in_inactive)
+            .field("scopes", &self.scopes)
+            .finish()
+    }
+}
 
-        let client_b64 = base64::encode(client_credentials);
+impl From<&DRACOONConnection> for SerializedConnection {
+    fn from(conn: &DRACOONConnection) -> Self {
+        let tokens = conn.tokens();
+
+        SerializedConnection {
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token,
+            connected_at: tokens.connected_at,
+            last_activity: conn.last_activity(),
+            expires_in: tokens.access_token_validity,
+            expires_in_inactive: tokens.inactivity_timeout,
+            scopes: conn.scopes.clone(),
+        }
+    }
+}
 
-        client_b64
+impl From<SerializedConnection> for DRACOONConnection {
+    fn from(stored: SerializedConnection) -> Self {
+        DRACOONConnection {
+            tokens: RwLock::new(ConnectionTokens {
+                connected_at: stored.connected_at,
+                access_token: stored.access_token,
+                access_token_validity: stored.expires_in,
+                refresh_token: stored.refresh_token,
+                refresh_token_validity: stored.expires_in_inactive,
+                absolute_expiry: stored.connected_at + chrono::Duration::seconds(stored.expires_in),
+                inactivity_timeout: stored.expires_in_inactive,
+                token_type: None,
+            }),
+            last_activity: Mutex::new(stored.last_activity),
+            scopes: stored.scopes,
+            auth_method: None,
+        }
     }
-    /// convert OAuth2TokenResponse to a connection item
-    fn create_connection(&mut self, token_response: &OAuth2TokenResponse) -> &Self {
-        let connection = DRACOONConnection {
-            connected_at: Utc::now(),
-            access_token: token_response.access_token.to_owned(),
-            refresh_token: token_response.refresh_token.to_owned(),
-            access_token_validity: token_response.expires_in_inactive,
-            refresh_token_validity: token_response.expires_in,
-        };
-        self.connection = Some(connection);
-        self.connected = true;
+}
 
-        self
+/// creates a connection from a token response (connected now, system clock - see
+/// DRACOONConnection::new for other clocks)
+/// - expires_in is the lifetime of the access token
+/// - expires_in_inactive is the sliding inactivity window
+impl From<&OAuth2TokenResponse> for DRACOONConnection {
+    fn from(token_response: &OAuth2TokenResponse) -> Self {
+        DRACOONConnection::new(token_response, &SystemClock)
     }
+}
 
-    fn get_token_url(&self) -> String {
-        format!("{}{}", self.base_url.to_string(), DRACOON_TOKEN_URL)
+impl DRACOONConnection {
+    /// creates a connection from a token response connected at the current time of the
+    /// given clock (clients use their own clock, see with_clock)
+    pub fn new(token_response: &OAuth2TokenResponse, clock: &dyn Clock) -> Self {
+        let connected_at = clock.now();
+        let tokens = ConnectionTokens::new(token_response, connected_at);
+
+        DRACOONConnection {
+            tokens: RwLock::new(tokens),
+            last_activity: Mutex::new(connected_at),
+            scopes: token_response
+                .scope
+                .split_whitespace()
+                .map(String::from)
+                .collect(),
+            auth_method: None,
+        }
     }
+}
 
-    fn get_connection(&self) -> Result<&DRACOONConnection, DRACOONClientError> {
-        match &self.connection {
-            Some(conn) => Ok(&conn),
-            None => Err(DRACOONClientError::BrokenConnection),
+/// read-only accessors for connection details (tokens stay private)
+impl DRACOONConnection {
+    /// snapshot of the current tokens and expiry
+    fn tokens(&self) -> ConnectionTokens {
+        match self.tokens.read() {
+            Ok(tokens) => tokens.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
         }
     }
 
-    pub fn check_access_token_validity(&self) -> Result<bool, DRACOONClientError> {
-        let conn = match &self.connection {
-            Some(conn) => conn,
-            None => return Err(DRACOONClientError::BrokenConnection),
-        };
+    /// replaces the tokens after a token refresh (connected at the given time)
+    pub(crate) fn refresh(&self, token_response: &OAuth2TokenResponse, now: DateTime<Utc>) {
+        let tokens = ConnectionTokens::new(token_response, now);
 
-        let now = Utc::now();
+        match self.tokens.write() {
+            Ok(mut current) => *current = tokens,
+            Err(poisoned) => *poisoned.into_inner() = tokens,
+        }
 
-        Ok((now - conn.connected_at).num_seconds() < conn.access_token_validity)
+        self.touch(now);
+    }
 
+    pub(crate) fn access_token(&self) -> String {
+        self.tokens().access_token
     }
 
-    /// authenticated ping
-    pub async fn test_connection(&self) -> Result<bool, DRACOONClientError> {
-        let api_url = format!("{}{}", &self.base_url, DRACOON_AUTHENTICATED_PING);
-        let conn = match self.get_connection() {
-            Ok(conn) => conn,
-            Err(e) => return Err(e),
-        };
+    pub(crate) fn refresh_token(&self) -> String {
+        self.tokens().refresh_token
+    }
 
-        let res = self
-            .http
-            .get(api_url)
-            .bearer_auth(&conn.access_token)
-            .send()
-            .await?;
+    pub fn connected_at(&self) -> DateTime<Utc> {
+        self.tokens().connected_at
+    }
 
-        match res.status() {
-            reqwest::StatusCode::OK => Ok(true),
-            _ => Ok(false),
-        }
+    /// scopes granted for the access token
+    pub fn scopes(&self) -> &[String] {
+        &self.scopes
     }
 
-    pub async fn disconnect(mut self, revoke_refresh: Option<bool>) -> Result<Self, DRACOONClientError> { 
+    /// owned summary of the connection (no tokens)
+    pub fn info(&self) -> ConnectionInfo {
+        let tokens = self.tokens();
+
+        ConnectionInfo {
+            connected_at: tokens.connected_at,
+            expires_at: tokens.absolute_expiry,
+            inactivity_expires_at: self.inactivity_expiry(),
+            expires_in: Duration::from_secs(tokens.access_token_validity.max(0) as u64),
+            expires_in_inactive: Duration::from_secs(tokens.inactivity_timeout.max(0) as u64),
+            scopes: self.scopes.clone(),
+            token_type: tokens.token_type,
+            auth_method: self.auth_method,
+        }
+    }
 
-        let conn = match self.get_connection() {
-            Ok(conn) => conn,
-            Err(e) => return Err(DRACOONClientError::BrokenConnection),
-        };
+    /// OAuth2 flow the connection was established with (None if created from a token
+    /// response directly)
+    pub fn auth_method(&self) -> Option<AuthMethod> {
+        self.auth_method
+    }
 
-        let revoke_url = format!("{}{}", &self.base_url, DRACOON_TOKEN_REVOKE_URL);
+    /// absolute expiry of the access token (connected_at + expires_in)
+    pub fn absolute_expiry(&self) -> DateTime<Utc> {
+        self.tokens().absolute_expiry
+    }
 
-        let revoke_access = OAuth2TokenRevoke { token: conn.access_token.clone().to_owned(), token_type_hint: TOKEN_TYPE_HINT_ACCESS.to_string(), client_id: self.client_id.clone(), client_secret: self.client_secret.clone()};
-        
-        let res = &self.http
-        .post(&revoke_url)
-        .form(&revoke_access)
-        .send()
-        .await?;
+    /// timestamp of the last successful authenticated request
+    pub fn last_activity(&self) -> DateTime<Utc> {
+        match self.last_activity.lock() {
+            Ok(last_activity) => *last_activity,
+            Err(_) => self.connected_at(),
+        }
+    }
 
+    /// sliding expiry due to inactivity (last_activity + expires_in_inactive)
+    pub fn inactivity_expiry(&self) -> DateTime<Utc> {
+        self.last_activity() + chrono::Duration::seconds(self.tokens().inactivity_timeout)
+    }
 
+    /// a connection is valid until either the absolute or the inactivity expiry is reached
+    pub fn is_valid_at(&self, now: DateTime<Utc>) -> bool {
+        now < self.absolute_expiry() && now < self.inactivity_expiry()
+    }
 
-        match res.status() {
-            reqwest::StatusCode::OK => {
+    /// resets the inactivity window (called on each successful authenticated request)
+    pub(crate) fn touch(&self, now: DateTime<Utc>) {
+        if let Ok(mut last_activity) = self.last_activity.lock() {
+            *last_activity = now;
+        }
+    }
 
-                Ok(self)
+    pub fn access_token_validity(&self) -> i64 {
+        self.tokens().access_token_validity
+    }
 
-            },
-            _ => Err(DRACOONClientError::BrokenConnection),
+    pub fn refresh_token_validity(&self) -> i64 {
+        self.tokens().refresh_token_validity
+    }
+}
 
+/// builder implementation for DRACOON client
+impl DRACOONClientBuilder {
+    pub fn new(base_url: Url, client_id: String, client_secret: String) -> DRACOONClientBuilder {
+        DRACOONClientBuilder {
+            base_url,
+            client_id,
+            client_secret,
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            max_buffered_download_size: DEFAULT_MAX_BUFFERED_DOWNLOAD_SIZE,
+            max_response_body_size: DEFAULT_MAX_RESPONSE_BODY_SIZE,
+            debug_mode: false,
+            default_page_size: DEFAULT_PAGE_SIZE,
+            max_retries: DEFAULT_MAX_RETRIES,
+            language: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: None,
+            timeout: None,
+            root_certificates: Vec::new(),
+            accept_invalid_certs: false,
+            date_format: None,
+            warning_callback: None,
+            shared_http: None,
+            metadata_cache_ttl: None,
+            dry_run: false,
+            method_override: false,
+            clock: None,
+            scopes: None,
         }
+    }
 
-
+    /// limits the number of authenticated requests in flight at the same time
+    /// (default: DEFAULT_MAX_CONCURRENT_REQUESTS) - the limit is global to the client:
+    /// every operation acquires a slot of the same semaphore, which is held in an Arc
+    /// and therefore shared across clones of the client
+    pub fn with_max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.max_concurrent_requests = max_concurrent_requests.max(1);
+        self
     }
 
-    /// main connect method
-    pub async fn connect(
-        &mut self,
-        connection_type: OAuth2ConnectionType,
-    ) -> Result<&DRACOONConnection, DRACOONClientError> {
-        let token_response = match connection_type {
-            OAuth2ConnectionType::AuthCode(auth_code) => self.connect_auth_code(auth_code).await,
-            OAuth2ConnectionType::PasswordFlow(user_name, password) => {
-                self.connect_password_flow(user_name, password).await
-            }
-            OAuth2ConnectionType::RefreshToken => self.connect_refresh_token().await,
-        };
+    /// sets the size cap for downloads buffered in memory (download_to_bytes, download_to_string)
+    /// - larger files return a FileTooLarge error (default: DEFAULT_MAX_BUFFERED_DOWNLOAD_SIZE)
+    pub fn with_max_buffered_download_size(mut self, max_buffered_download_size: u64) -> Self {
+        self.max_buffered_download_size = max_buffered_download_size;
+        self
+    }
 
-        let result = match token_response {
-            Ok(t) => t,
-            Err(e) => return Err(e),
-        };
+    /// sets the size cap for API response bodies (JSON models and error responses) - larger
+    /// bodies return a ResponseTooLarge error without being buffered completely (default:
+    /// DEFAULT_MAX_RESPONSE_BODY_SIZE) - downloads are streamed and not affected
+    pub fn with_max_response_body_size(mut self, max_response_body_size: u64) -> Self {
+        self.max_response_body_size = max_response_body_size;
+        self
+    }
 
-        self.create_connection(&result);
+    /// enables debug mode: if a response cannot be deserialized into the expected model,
+    /// the raw response body is attached to the returned DecodeError (default: off)
+    pub fn with_debug_mode(mut self, debug_mode: bool) -> Self {
+        self.debug_mode = debug_mode;
+        self
+    }
 
-        match &self.connection {
-            Some(c) => Ok(c),
-            None => Err(DRACOONClientError::BrokenConnection),
-        }
+    /// sets the page size (limit) used by list calls without explicit limit (default and
+    /// max: 500) - values are clamped to 1..=500 to avoid 400 errors
+    pub fn with_default_page_size(mut self, default_page_size: u64) -> Self {
+        self.default_page_size = default_page_size.clamp(1, DEFAULT_PAGE_SIZE);
+        self
     }
 
-    async fn parse_login_response(
-        &self,
-        res: Response,
-    ) -> Result<OAuth2TokenResponse, DRACOONClientError> {
-        match res.status() {
-            reqwest::StatusCode::OK => Ok(res.json::<OAuth2TokenResponse>().await?),
-            _ => Err(DRACOONClientError::DRACOONErrror(
-                res.json::<DRACOONErrorResponse>().await?,
-            )),
-        }
+    /// sets the number of retries on transient failures (connection errors, timeouts,
+    /// 429, 502, 503, 504) with exponential backoff (default: DEFAULT_MAX_RETRIES, 0 disables)
+    /// - a Retry-After header of the response (seconds or HTTP date) replaces the backoff,
+    ///   both are capped at 30 seconds
+    /// - only idempotent operations are retried: GET requests, PUT updates by id, deletes
+    ///   by id, token refresh and single upload chunks (idempotent via Content-Range)
+    /// - never retried: creating nodes, groups or upload channels and upload finalization,
+    ///   which could create duplicates
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
     }
 
-    async fn connect_password_flow(
-        &self,
-        user_name: String,
-        password: String,
-    ) -> Result<OAuth2TokenResponse, DRACOONClientError> {
- 
-            let client_b64 = self.client_credentials();
+    /// sets the Accept-Language header on all requests (e.g. "de-DE") so that error
+    /// messages are returned in the given locale (default: no header, server default)
+    pub fn with_language(mut self, language: &str) -> Self {
+        self.language = Some(language.to_string());
+        self
+    }
 
-            let token_url = self.get_token_url();
+    /// sets the max number of idle connections kept per host (forwarded to reqwest,
+    /// default: no limit) - for chatty clients a value matching max_concurrent_requests
+    /// keeps enough connections open to avoid repeated TLS handshakes
+    pub fn with_pool_max_idle_per_host(mut self, pool_max_idle_per_host: usize) -> Self {
+        self.pool_max_idle_per_host = Some(pool_max_idle_per_host);
+        self
+    }
 
-            let auth_header = format!("Basic {}", client_b64);
+    /// sets the timeout for idle connections in the pool (forwarded to reqwest,
+    /// default: 90 seconds, None disables the timeout) - for chatty clients with
+    /// pauses between bursts, a longer timeout (e.g. 5 minutes) reduces connection churn
+    pub fn with_pool_idle_timeout(mut self, pool_idle_timeout: Option<Duration>) -> Self {
+        self.pool_idle_timeout = Some(pool_idle_timeout);
+        self
+    }
 
-            let auth = OAuth2PasswordFlow {
-                username: user_name,
-                password: password,
-                grant_type: GRANT_TYPE_PASSWORD.to_string(),
-            };
+    /// sets the total timeout of a single request (forwarded to reqwest, default: no
+    /// timeout) - applies to file transfers as well, so choose it large enough for chunks
+    /// or override it per transfer (DownloadOptions / UploadOptions timeout)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
 
-            let res = self
-                .http
-                .post(token_url)
-                .form(&auth)
-                .header(AUTHORIZATION, auth_header)
-                .send()
-                .await?;
+    /// adds a trusted root certificate (PEM) in addition to the system roots, e.g. the
+    /// internal CA of an on-prem DRACOON instance - returns InvalidCertificate if the
+    /// PEM cannot be parsed
+    pub fn add_root_certificate(mut self, pem: &[u8]) -> Result<Self, DRACOONClientError> {
+        let certificate =
+            Certificate::from_pem(pem).map_err(DRACOONClientError::InvalidCertificate)?;
+        self.root_certificates.push(certificate);
+        Ok(self)
+    }
 
-            match self.parse_login_response(res).await {
-                Ok(res) => Ok(res),
-                Err(err) => Err(err),
-            }
-      
+    /// adds a trusted root certificate from a PEM file (see add_root_certificate)
+    pub fn add_root_certificate_from_file<P: AsRef<Path>>(
+        self,
+        path: P,
+    ) -> Result<Self, DRACOONClientError> {
+        let pem = std::fs::read(path)?;
+        self.add_root_certificate(&pem)
     }
 
-    async fn connect_refresh_token(&self) -> Result<OAuth2TokenResponse, DRACOONClientError> {
-        let refresh_token: String;
+    /// sets the date format of responses (X-Sds-Date-Format header, default: server default)
+    pub fn with_date_format(mut self, date_format: DateFormat) -> Self {
+        self.date_format = Some(date_format);
+        self
+    }
 
-        match &self.connection {
-            Some(connection) => refresh_token = connection.refresh_token.clone(),
-            None => return Err(DRACOONClientError::BrokenConnection),
+    /// sets a callback for deprecation / warning headers in responses (X-Sds-* deprecation
+    /// or warning headers, Deprecation, Sunset, Warning) - they are also logged via
+    /// tracing if the tracing feature is enabled
+    pub fn with_warning_callback<F: Fn(&ApiWarning) + Send + Sync + 'static>(
+        mut self,
+        callback: F,
+    ) -> Self {
+        self.warning_callback = Some(Arc::new(callback));
+        self
+    }
+
+    /// DANGER: disables TLS certificate verification (forwarded to reqwest, default: off)
+    /// - only meant for local development against self-signed servers, never use it in
+    ///   production (prefer add_root_certificate for private CAs)
+    pub fn danger_accept_invalid_certs(mut self, accept_invalid_certs: bool) -> Self {
+        self.accept_invalid_certs = accept_invalid_certs;
+        self
+    }
+
+    /// uses an existing reqwest client (and its connection pool) instead of creating a new
+    /// one - the HTTP settings of this builder (language, date format, pool, certificates)
+    /// are not applied to a shared client
+    pub fn with_shared_http(mut self, http: Client) -> Self {
+        self.shared_http = Some(http);
+        self
+    }
+
+    /// sets the OAuth scopes requested by the password flow and the default authorize url
+    /// (default: scope of the OAuth app, "all" for the authorize url)
+    pub fn with_scopes(mut self, scopes: Vec<String>) -> Self {
+        self.scopes = Some(scopes);
+        self
+    }
+
+    /// sets the clock used for all expiry logic (default: SystemClock) - e.g. a MockClock
+    /// to test token expiry deterministically
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// enables an in-memory cache for node metadata (get_node, get_nodes; default: off):
+    /// responses are reused for the given TTL (client clock, see with_clock), afterwards
+    /// they are revalidated with If-None-Match and reused on 304 Not Modified (endpoints
+    /// without ETag are fetched again) - any non-GET request of the client clears the cache
+    pub fn with_metadata_cache(mut self, ttl: Duration) -> Self {
+        self.metadata_cache_ttl = Some(ttl);
+        self
+    }
+
+    /// enables dry run mode (default: off, see DRACOONClient::set_dry_run)
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// tunnels PUT, DELETE and PATCH requests to the DRACOON API as POST with an
+    /// X-HTTP-Method-Override header (default: off) - for proxies blocking these methods
+    /// - affects all updates (nodes, shares, users, groups, webhooks, notification
+    ///   config), deletes (nodes, shares, users, groups, subscriptions, webhooks, upload
+    ///   channels) and upload finalization
+    /// - moving and copying nodes already use POST - upload chunks and downloads go to
+    ///   presigned urls (possibly S3 storage) and are never tunnelled
+    pub fn with_method_override(mut self, method_override: bool) -> Self {
+        self.method_override = method_override;
+        self
+    }
+
+    /// creates the DRACOON client with the configured settings - returns InvalidUrl if the
+    /// base url cannot be used as a base (e.g. "mailto:...") or RequestFailed if the
+    /// HTTP client cannot be created (e.g. TLS backend errors)
+    pub fn build(self) -> Result<DRACOONClient, DRACOONClientError> {
+        if self.base_url.cannot_be_a_base() {
+            return Err(DRACOONClientError::InvalidUrl(
+                url::ParseError::RelativeUrlWithCannotBeABaseBase,
+            ));
+        }
+
+        // endpoint urls are appended to the base url (requires a trailing slash)
+        let endpoints = Endpoints::new(&self.base_url);
+
+        let shared_http = self.shared_http.is_some();
+
+        let config = ClientConfig {
+            base_url: endpoints.base_url().clone(),
+            api_prefix: DRACOON_API_PREFIX,
+            client_id: self.client_id.clone(),
+            user_agent: match shared_http {
+                true => None,
+                false => Some(APP_USER_AGENT.to_string()),
+            },
+            timeout: self.timeout,
+            pool_idle_timeout: match shared_http {
+                true => None,
+                false => self
+                    .pool_idle_timeout
+                    .unwrap_or(Some(DEFAULT_POOL_IDLE_TIMEOUT)),
+            },
+            proxy: match shared_http {
+                true => None,
+                false => Some(
+                    PROXY_ENV_VARS
+                        .iter()
+                        .any(|var| std::env::var_os(var).is_some_and(|value| !value.is_empty())),
+                ),
+            },
+            tls_backend: TLS_BACKEND,
+            accept_invalid_certs: self.accept_invalid_certs && !shared_http,
+            root_certificates: match shared_http {
+                true => 0,
+                false => self.root_certificates.len(),
+            },
+            shared_http,
+            max_retries: self.max_retries,
+            max_concurrent_requests: self.max_concurrent_requests,
+            default_page_size: self.default_page_size,
+            max_response_body_size: self.max_response_body_size,
+            method_override: self.method_override,
+        };
+
+        let http = match self.shared_http {
+            Some(http) => http,
+            None => {
+                let mut http = Client::builder().user_agent(APP_USER_AGENT);
+
+                let mut headers = HeaderMap::new();
+
+                if let Some(language) = self.language {
+                    if let Ok(language) = HeaderValue::from_str(&language) {
+                        headers.insert(ACCEPT_LANGUAGE, language);
+                    }
+                }
+
+                if let Some(date_format) = self.date_format {
+                    headers.insert(
+                        DRACOON_DATE_FORMAT_HEADER,
+                        HeaderValue::from_static(date_format.as_header_value()),
+                    );
+                }
+
+                if !headers.is_empty() {
+                    http = http.default_headers(headers);
+                }
+
+                if let Some(pool_max_idle_per_host) = self.pool_max_idle_per_host {
+                    http = http.pool_max_idle_per_host(pool_max_idle_per_host);
+                }
+
+                if let Some(pool_idle_timeout) = self.pool_idle_timeout {
+                    http = http.pool_idle_timeout(pool_idle_timeout);
+                }
+
+                if let Some(timeout) = self.timeout {
+                    http = http.timeout(timeout);
+                }
+
+                for certificate in self.root_certificates {
+                    http = http.add_root_certificate(certificate);
+                }
+
+                if self.accept_invalid_certs {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!("TLS certificate verification is disabled (danger_accept_invalid_certs)");
+                    http = http.danger_accept_invalid_certs(true);
+                }
+
+                http.build()?
+            }
+        };
+
+        Ok(DRACOONClient {
+            endpoints,
+            client_id: self.client_id,
+            client_secret: self.client_secret,
+            http,
+            session: Arc::new(RwLock::new(Session::default())),
+            limiter: Arc::new(Semaphore::new(self.max_concurrent_requests)),
+            transferred: Arc::new(TransferCounters::default()),
+            max_buffered_download_size: self.max_buffered_download_size,
+            method_override: self.method_override,
+            max_response_body_size: self.max_response_body_size,
+            debug_mode: self.debug_mode,
+            default_page_size: self.default_page_size,
+            max_retries: self.max_retries,
+            warning_callback: self.warning_callback,
+            config,
+            refresh_lock: Arc::new(tokio::sync::Mutex::new(())),
+            dry_run: Arc::new(AtomicBool::new(self.dry_run)),
+            api_version: Arc::new(OnceCell::new()),
+            clock: self.clock.unwrap_or_else(|| Arc::new(SystemClock)),
+            scope: self.scopes.map(|scopes| scopes.join(" ")),
+            rate_limit: Arc::new(Mutex::new(None)),
+            metadata_cache: self.metadata_cache_ttl.map(|ttl| {
+                Arc::new(MetadataCache {
+                    ttl,
+                    entries: Mutex::new(HashMap::new()),
+                })
+            }),
+        })
+    }
+}
+
+/// position of a JSON items splitter in a list response ({"range": .., "items": [..]})
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ItemsState {
+    /// looking for the "items" key in the top level object
+    Seek,
+    /// "items" key found, waiting for the array
+    Array,
+    /// inside the items array
+    Items,
+    /// items array finished (or missing)
+    Done,
+}
+
+/// splits the items of a JSON list response into one buffer per item while the body is
+/// received - only the current item is buffered
+struct JsonItemsSplitter {
+    state: ItemsState,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    /// last string at the top level (to detect the "items" key)
+    key: Vec<u8>,
+    item: Option<Vec<u8>>,
+}
+
+impl JsonItemsSplitter {
+    fn new() -> Self {
+        JsonItemsSplitter {
+            state: ItemsState::Seek,
+            depth: 0,
+            in_string: false,
+            escaped: false,
+            key: Vec::new(),
+            item: None,
+        }
+    }
+
+    /// feeds a chunk of the body - returns the items completed by it (ResponseTooLarge if
+    /// a single item exceeds the given size)
+    fn push(&mut self, chunk: &[u8], max_item_size: u64) -> Result<Vec<Vec<u8>>, DRACOONClientError> {
+        let mut items = Vec::new();
+
+        for &byte in chunk {
+            let at_items = self.state == ItemsState::Items && self.depth == 2;
+
+            if self.in_string {
+                if let Some(item) = &mut self.item {
+                    item.push(byte);
+                } else if self.depth == 1 && byte != b'"' {
+                    self.key.push(byte);
+                }
+
+                match (self.escaped, byte) {
+                    (true, _) => self.escaped = false,
+                    (false, b'\\') => self.escaped = true,
+                    (false, b'"') => self.in_string = false,
+                    _ => (),
+                }
+
+                continue;
+            }
+
+            if byte.is_ascii_whitespace() {
+                if let Some(item) = &mut self.item {
+                    item.push(byte);
+                }
+                continue;
+            }
+
+            if self.state == ItemsState::Array {
+                match byte {
+                    b'[' => {
+                        self.state = ItemsState::Items;
+                        self.depth += 1;
+                    }
+                    _ => self.state = ItemsState::Done,
+                }
+                continue;
+            }
+
+            // end of a scalar item or of the items array
+            if at_items && matches!(byte, b',' | b']') {
+                if let Some(item) = self.item.take() {
+                    items.push(item);
+                }
+                if byte == b']' {
+                    self.state = ItemsState::Done;
+                    self.depth -= 1;
+                }
+                continue;
+            }
+
+            if at_items && self.item.is_none() {
+                self.item = Some(Vec::new());
+            }
+
+            if let Some(item) = &mut self.item {
+                item.push(byte);
+            }
+
+            match byte {
+                b'"' => {
+                    self.in_string = true;
+                    if self.depth == 1 {
+                        self.key.clear();
+                    }
+                }
+                b'{' | b'[' => self.depth += 1,
+                b'}' | b']' => {
+                    self.depth = self.depth.saturating_sub(1);
+
+                    if self.state == ItemsState::Items && self.depth == 2 {
+                        if let Some(item) = self.item.take() {
+                            items.push(item);
+                        }
+                    }
+                }
+                b':' if self.depth == 1 && self.state == ItemsState::Seek && self.key == b"items" => {
+                    self.state = ItemsState::Array;
+                }
+                _ => (),
+            }
+
+            if self.item.as_ref().is_some_and(|item| item.len() as u64 > max_item_size) {
+                return Err(DRACOONClientError::ResponseTooLarge);
+            }
+        }
+
+        Ok(items)
+    }
+}
+
+/// page of a list response being read (see open_list_page) - large bodies are
+/// deserialized item by item while they are received (memory stays at about one item),
+/// small bodies are parsed at once
+pub(crate) struct ListPage<T> {
+    res: Option<Response>,
+    splitter: JsonItemsSplitter,
+    items: VecDeque<T>,
+    count: u64,
+}
+
+impl<T> ListPage<T> {
+    /// number of items read from the page so far
+    pub(crate) fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+/// reads an API response body (JSON models, errors) up to the given size - returns
+/// ResponseTooLarge as soon as the limit is exceeded instead of buffering the rest
+async fn read_body(mut res: Response, max_size: u64) -> Result<String, DRACOONClientError> {
+    if res.content_length().is_some_and(|length| length > max_size) {
+        return Err(DRACOONClientError::ResponseTooLarge);
+    }
+
+    let charset = response_charset(&res);
+
+    let mut body = Vec::new();
+
+    while let Some(chunk) = res.chunk().await? {
+        if (body.len() + chunk.len()) as u64 > max_size {
+            return Err(DRACOONClientError::ResponseTooLarge);
+        }
+
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(decode_text(&body, charset.as_deref()))
+}
+
+/// charset parameter of the Content-Type header (lowercase, e.g. "utf-8")
+fn response_charset(res: &Response) -> Option<String> {
+    res.headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| {
+            value.split(';').skip(1).find_map(|param| {
+                let (name, charset) = param.split_once('=')?;
+
+                match name.trim().eq_ignore_ascii_case("charset") {
+                    true => Some(charset.trim().trim_matches('"').to_ascii_lowercase()),
+                    false => None,
+                }
+            })
+        })
+}
+
+/// decodes a response body - Latin-1 charsets are mapped byte by byte, everything else
+/// is read as UTF-8 (invalid sequences replaced), a leading BOM is dropped
+fn decode_text(body: &[u8], charset: Option<&str>) -> String {
+    match charset {
+        Some(charset) if LATIN1_CHARSETS.contains(&charset) => {
+            body.iter().map(|byte| *byte as char).collect()
+        }
+        _ => {
+            let body = body.strip_prefix(UTF8_BOM).unwrap_or(body);
+
+            String::from_utf8_lossy(body).into_owned()
+        }
+    }
+}
+
+/// parses an error response body and attaches the request id from the headers - empty
+/// or non-JSON bodies (e.g. HTML from a gateway) return a GatewayError instead
+async fn read_error_response(
+    res: Response,
+    max_body_size: u64,
+) -> Result<DRACOONErrorResponse, DRACOONClientError> {
+    let status = res.status();
+
+    let request_id = res
+        .headers()
+        .get(DRACOON_REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string());
+
+    let is_json = res
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_ascii_lowercase().contains("json"))
+        .unwrap_or(false);
+
+    let body = read_body(res, max_body_size).await?;
+
+    let gateway_error = |body: &str| DRACOONClientError::GatewayError {
+        status,
+        body_snippet: body.trim().chars().take(ERROR_BODY_SNIPPET_LENGTH).collect(),
+    };
+
+    if !is_json {
+        return Err(gateway_error(&body));
+    }
+
+    let mut error = serde_json::from_str::<DRACOONErrorResponse>(&body)
+        .map_err(|_| gateway_error(&body))?;
+    error.request_id = request_id;
+
+    Ok(error)
+}
+
+/// runs a transfer (file content up- or download) with an optional timeout - returns Timeout
+/// once it is exceeded (the transfer future is dropped)
+pub(crate) async fn with_transfer_timeout<T, F>(
+    timeout: Option<Duration>,
+    transfer: F,
+) -> Result<T, DRACOONClientError>
+where
+    F: Future<Output = Result<T, DRACOONClientError>>,
+{
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, transfer)
+            .await
+            .unwrap_or(Err(DRACOONClientError::Timeout)),
+        None => transfer.await,
+    }
+}
+
+/// dropping a connected client does not revoke its tokens (see close) - dropping a clone
+/// while other clones still use the session is fine
+impl Drop for DRACOONClient {
+    fn drop(&mut self) {
+        #[cfg(feature = "tracing")]
+        if Arc::strong_count(&self.session) == 1 && self.is_connected() {
+            tracing::warn!("DRACOON client dropped while connected - tokens were not revoked (use close)");
+        }
+    }
+}
+
+/// handle for a background keep-alive task - the task stops when the handle is dropped
+pub struct KeepAliveHandle {
+    task: JoinHandle<()>,
+}
+
+impl KeepAliveHandle {
+    /// stops the keep-alive task
+    pub fn stop(self) {
+        self.task.abort();
+    }
+}
+
+impl Drop for KeepAliveHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// supported OAuth2 flows by client
+pub enum OAuth2ConnectionType {
+    PasswordFlow(String, String),
+    AuthCode(String),
+    RefreshToken,
+}
+
+/// OAuth2 flow a connection was established with (no credentials)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AuthMethod {
+    Password,
+    AuthCode,
+    RefreshToken,
+}
+
+impl From<&OAuth2ConnectionType> for AuthMethod {
+    fn from(connection_type: &OAuth2ConnectionType) -> Self {
+        match connection_type {
+            OAuth2ConnectionType::PasswordFlow(_, _) => AuthMethod::Password,
+            OAuth2ConnectionType::AuthCode(_) => AuthMethod::AuthCode,
+            OAuth2ConnectionType::RefreshToken => AuthMethod::RefreshToken,
+        }
+    }
+}
+
+/// branding of the DRACOON login page in authorization code flow
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Branding {
+    Full,
+    Basic,
+    None,
+}
+
+impl Branding {
+    fn as_query_value(&self) -> &'static str {
+        match self {
+            Branding::Full => "full",
+            Branding::Basic => "basic",
+            Branding::None => "none",
+        }
+    }
+}
+
+/// parameters of the authorize url (authorization code flow) - the default matches the
+/// url of get_code_url (full branding, response type "code", scope "all", no state)
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthorizeUrlOptions {
+    pub branding: Branding,
+    pub response_type: String,
+    pub scope: String,
+    pub state: Option<String>,
+}
+
+impl Default for AuthorizeUrlOptions {
+    fn default() -> Self {
+        AuthorizeUrlOptions {
+            branding: Branding::Full,
+            response_type: "code".to_string(),
+            scope: "all".to_string(),
+            state: None,
+        }
+    }
+}
+
+/// builds the authorize url for authorization code flow - the query parameters are
+/// always in the same order (branding, response_type, client_id, redirect_uri, scope,
+/// state) and url encoded
+/// - the redirect uri is the DRACOON callback url also used to exchange the code
+pub fn authorize_url(base_url: &Url, client_id: &str, options: &AuthorizeUrlOptions) -> Url {
+    let endpoints = Endpoints::new(base_url);
+    let mut url = endpoints.authorize();
+
+    let redirect_uri = endpoints.redirect();
+
+    {
+        let mut query = url.query_pairs_mut();
+        query
+            .append_pair("branding", options.branding.as_query_value())
+            .append_pair("response_type", &options.response_type)
+            .append_pair("client_id", client_id)
+            .append_pair("redirect_uri", &redirect_uri)
+            .append_pair("scope", &options.scope);
+
+        if let Some(state) = &options.state {
+            query.append_pair("state", state);
+        }
+    }
+
+    url
+}
+
+/// core connection implementation for DRACOON client
+impl DRACOONClient {
+    /// creates a new DRACOON client instance with given OAuth app credentials and base URL
+    /// - returns InvalidUrl if the base URL cannot be parsed
+    pub fn new(
+        base_url: &str,
+        client_id: String,
+        client_secret: String,
+    ) -> Result<DRACOONClient, DRACOONClientError> {
+        let base_url = Url::parse(base_url)?;
+
+        DRACOONClientBuilder::new(base_url, client_id, client_secret).build()
+    }
+
+    /// creates a DRACOON client sharing an existing reqwest client (e.g. one client per
+    /// identity in a multi-tenant service sharing one connection pool) - the reqwest
+    /// client is cheap to clone (reference counted)
+    pub fn with_shared_http(
+        http: Client,
+        base_url: Url,
+        client_id: String,
+        client_secret: String,
+    ) -> Result<DRACOONClient, DRACOONClientError> {
+        DRACOONClientBuilder::new(base_url, client_id, client_secret)
+            .with_shared_http(http)
+            .build()
+    }
+
+    /// returns a builder to configure optional client settings
+    pub fn builder(base_url: Url, client_id: String, client_secret: String) -> DRACOONClientBuilder {
+        DRACOONClientBuilder::new(base_url, client_id, client_secret)
+    }
+
+    /// sends an authenticated request (bearer token) - waits for a free slot of the
+    /// client's concurrency limiter before sending (never retried)
+    pub(crate) async fn send_authenticated(
+        &self,
+        req: RequestBuilder,
+    ) -> Result<Response, DRACOONClientError> {
+        self.send_authenticated_with(req, false).await
+    }
+
+    /// sends an idempotent authenticated request - transient failures are retried
+    pub(crate) async fn send_authenticated_idempotent(
+        &self,
+        req: RequestBuilder,
+    ) -> Result<Response, DRACOONClientError> {
+        self.send_authenticated_with(req, true).await
+    }
+
+    async fn send_authenticated_with(
+        &self,
+        req: RequestBuilder,
+        idempotent: bool,
+    ) -> Result<Response, DRACOONClientError> {
+        let conn = self.get_connection()?;
+
+        // writes may change any cached metadata (e.g. moving a node changes two parents)
+        if let Some(cache) = &self.metadata_cache {
+            let is_get = req
+                .try_clone()
+                .and_then(|req| req.build().ok())
+                .is_some_and(|req| req.method() == Method::GET);
+
+            if !is_get {
+                cache.clear();
+            }
+        }
+
+        let access_token = conn.access_token();
+        // kept to resend the request once after a token refresh (not for streaming bodies)
+        let retry_req = req.try_clone();
+        let req = req.bearer_auth(&access_token);
+
+        let _permit = self
+            .limiter
+            .acquire()
+            .await
+            .map_err(|_| DRACOONClientError::BrokenConnection)?;
+
+        self.transferred.requests.fetch_add(1, Ordering::Relaxed);
+
+        let res = self.send_once(req, idempotent).await?;
+
+        // token rejected earlier than expected (revoked server-side, clock skew): refresh
+        // and retry once - the original 401 is returned if that fails as well
+        let res = match (res.status(), retry_req) {
+            (reqwest::StatusCode::UNAUTHORIZED, Some(retry_req)) => {
+                match self.refresh_after_unauthorized(&conn, &access_token).await {
+                    Ok(()) => {
+                        let retry_req = retry_req.bearer_auth(conn.access_token());
+
+                        match self.send_once(retry_req, idempotent).await {
+                            Ok(retried) if retried.status() != reqwest::StatusCode::UNAUTHORIZED => {
+                                retried
+                            }
+                            _ => res,
+                        }
+                    }
+                    Err(_) => res,
+                }
+            }
+            _ => res,
+        };
+
+        if res.status().is_success() {
+            conn.touch(self.now());
+        }
+
+        Ok(res)
+    }
+
+    /// sends a request once (non-idempotent) or with retries for transient failures
+    async fn send_once(
+        &self,
+        req: RequestBuilder,
+        idempotent: bool,
+    ) -> Result<Response, DRACOONClientError> {
+        match idempotent {
+            true => self.send_with_retry(req).await,
+            false => {
+                let res = req.send().await?;
+                self.inspect_response(&res);
+                Ok(res)
+            }
+        }
+    }
+
+    /// refreshes the access token after a 401 - concurrent requests rejected with the
+    /// same token share one refresh (the refresh token may only be used once)
+    async fn refresh_after_unauthorized(
+        &self,
+        conn: &DRACOONConnection,
+        rejected_token: &str,
+    ) -> Result<(), DRACOONClientError> {
+        let _guard = self.refresh_lock.lock().await;
+
+        if conn.access_token() != rejected_token {
+            return Ok(());
+        }
+
+        let token_response = self.connect_refresh_token().await?;
+        conn.refresh(&token_response, self.now());
+
+        Ok(())
+    }
+
+    /// sends an idempotent request without bearer token (chunk uploads, download urls) with
+    /// retries - waits for a free slot of the client's concurrency limiter like
+    /// authenticated requests (held until the response headers are received)
+    pub(crate) async fn send_limited(
+        &self,
+        req: RequestBuilder,
+    ) -> Result<Response, DRACOONClientError> {
+        let _permit = self
+            .limiter
+            .acquire()
+            .await
+            .map_err(|_| DRACOONClientError::BrokenConnection)?;
+
+        self.send_with_retry(req).await
+    }
+
+    /// sends a request and retries transient failures (connection errors, timeouts, 429,
+    /// 502, 503, 504) with exponential backoff or after the Retry-After of the response -
+    /// only use for idempotent requests
+    pub(crate) async fn send_with_retry(
+        &self,
+        req: RequestBuilder,
+    ) -> Result<Response, DRACOONClientError> {
+        let res = self.send_with_retry_attempts(req).await?;
+        self.inspect_response(&res);
+
+        Ok(res)
+    }
+
+    async fn send_with_retry_attempts(
+        &self,
+        req: RequestBuilder,
+    ) -> Result<Response, DRACOONClientError> {
+        let mut attempt = 0;
+
+        loop {
+            // requests with streaming bodies cannot be cloned and are sent once
+            let current = match req.try_clone() {
+                Some(current) => current,
+                None => return Ok(req.send().await?),
+            };
+
+            let result = current.send().await;
+
+            let transient = match &result {
+                Ok(res) => matches!(
+                    res.status(),
+                    reqwest::StatusCode::TOO_MANY_REQUESTS
+                        | reqwest::StatusCode::BAD_GATEWAY
+                        | reqwest::StatusCode::SERVICE_UNAVAILABLE
+                        | reqwest::StatusCode::GATEWAY_TIMEOUT
+                ),
+                Err(err) => err.is_connect() || err.is_timeout(),
+            };
+
+            if !transient || attempt >= self.max_retries {
+                return Ok(result?);
+            }
+
+            let delay = result
+                .ok()
+                .and_then(|res| self.retry_after(&res))
+                .unwrap_or_else(|| RETRY_BASE_DELAY.saturating_mul(2u32.saturating_pow(attempt)));
+
+            tokio::time::sleep(delay.min(RETRY_MAX_DELAY)).await;
+            attempt += 1;
+        }
+    }
+
+    /// parses the Retry-After header of a response - delay in seconds or HTTP date (past
+    /// dates retry immediately)
+    fn retry_after(&self, res: &Response) -> Option<Duration> {
+        let retry_after = res.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+        if let Ok(seconds) = retry_after.parse::<u64>() {
+            return Some(Duration::from_secs(seconds));
+        }
+
+        let retry_at = DateTime::parse_from_rfc2822(retry_after).ok()?;
+
+        Some(
+            retry_at
+                .with_timezone(&Utc)
+                .signed_duration_since(self.now())
+                .to_std()
+                .unwrap_or(Duration::ZERO),
+        )
+    }
+
+    /// handles the headers of every API response (warnings and rate limit)
+    fn inspect_response(&self, res: &Response) {
+        self.report_warnings(res);
+        self.record_rate_limit(res);
+    }
+
+    /// keeps the rate limit headers of a response (see rate_limit_status) - responses
+    /// without rate limit headers keep the previous status
+    fn record_rate_limit(&self, res: &Response) {
+        let header = |name: &str| {
+            res.headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.trim().to_string())
+        };
+
+        let limit = header(RATE_LIMIT_LIMIT_HEADER);
+        let remaining = header(RATE_LIMIT_REMAINING_HEADER);
+        let reset = header(RATE_LIMIT_RESET_HEADER);
+
+        if limit.is_none() && remaining.is_none() && reset.is_none() {
+            return;
+        }
+
+        let received_at = self.now();
+
+        let reset_at = reset
+            .and_then(|reset| reset.parse::<i64>().ok())
+            .and_then(|reset| match reset >= RATE_LIMIT_RESET_TIMESTAMP_MIN {
+                true => chrono::NaiveDateTime::from_timestamp_opt(reset, 0)
+                    .map(|reset_at| DateTime::from_utc(reset_at, Utc)),
+                false => received_at.checked_add_signed(chrono::Duration::seconds(reset)),
+            });
+
+        let status = RateLimitStatus {
+            limit: limit.and_then(|limit| limit.parse().ok()),
+            remaining: remaining.and_then(|remaining| remaining.parse().ok()),
+            reset_at,
+            received_at,
+        };
+
+        match self.rate_limit.lock() {
+            Ok(mut current) => *current = Some(status),
+            Err(poisoned) => *poisoned.into_inner() = Some(status),
+        }
+    }
+
+    /// rate limit reported by the most recent response with rate limit headers (also on
+    /// successful responses) - None if no response contained them yet; use it to slow
+    /// down before DRACOON answers with 429 (reflects the last request only, concurrent
+    /// requests may have used up the remaining requests already)
+    pub fn rate_limit_status(&self) -> Option<RateLimitStatus> {
+        match self.rate_limit.lock() {
+            Ok(status) => status.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// reports deprecation / warning headers of a response (callback and tracing)
+    fn report_warnings(&self, res: &Response) {
+        for (name, value) in res.headers() {
+            if !is_warning_header(name.as_str()) {
+                continue;
+            }
+
+            let warning = ApiWarning {
+                url: res.url().to_string(),
+                header: name.to_string(),
+                value: value.to_str().unwrap_or_default().to_string(),
+            };
+
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                "DRACOON API warning for {}: {}: {}",
+                warning.url,
+                warning.header,
+                warning.value
+            );
+
+            if let Some(callback) = &self.warning_callback {
+                callback(&warning);
+            }
+        }
+    }
+
+    /// returns a snapshot of the effective client configuration (user agent, timeouts,
+    /// proxy, TLS, base url) to debug misconfiguration - never contains the client secret
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    /// enables or disables dry run mode at runtime: destructive node operations (delete,
+    /// move, upload with overwrite) are not sent and return a DryRun error describing the
+    /// skipped call instead - read operations are executed normally
+    pub fn set_dry_run(&self, dry_run: bool) {
+        self.dry_run.store(dry_run, Ordering::Relaxed);
+    }
+
+    /// checks if dry run mode is enabled
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run.load(Ordering::Relaxed)
+    }
+
+    /// guard for destructive operations - returns the skipped action in dry run mode
+    pub(crate) fn check_dry_run<F: FnOnce() -> DryRunAction>(
+        &self,
+        action: F,
+    ) -> Result<(), DRACOONClientError> {
+        if !self.is_dry_run() {
+            return Ok(());
+        }
+
+        let action = action();
+
+        #[cfg(feature = "tracing")]
+        tracing::info!("dry run - skipped {:?}", action);
+
+        Err(DRACOONClientError::DryRun(action))
+    }
+
+    /// total bytes uploaded (file content) by this client
+    pub fn bytes_uploaded(&self) -> u64 {
+        self.transferred.uploaded.load(Ordering::Relaxed)
+    }
+
+    /// total bytes downloaded (file content) by this client
+    pub fn bytes_downloaded(&self) -> u64 {
+        self.transferred.downloaded.load(Ordering::Relaxed)
+    }
+
+    /// number of requests currently holding a slot of the concurrency limiter (see
+    /// with_max_concurrent_requests) - counts all requests of the client (including chunk
+    /// uploads and downloads), also if it is shared between tasks (e.g. Arc<DRACOONClient>)
+    /// and keep-alive pings
+    pub fn in_flight_requests(&self) -> usize {
+        self.config
+            .max_concurrent_requests
+            .saturating_sub(self.limiter.available_permits())
+    }
+
+    /// total authenticated requests sent by this client (a request retried after a token
+    /// refresh or a transient failure counts once) - shared like in_flight_requests
+    pub fn total_requests(&self) -> u64 {
+        self.transferred.requests.load(Ordering::Relaxed)
+    }
+
+    /// resets the upload and download byte counters and the total request count
+    pub fn reset_counters(&self) {
+        self.transferred.uploaded.store(0, Ordering::Relaxed);
+        self.transferred.downloaded.store(0, Ordering::Relaxed);
+        self.transferred.requests.store(0, Ordering::Relaxed);
+    }
+
+    pub(crate) fn count_uploaded(&self, bytes: u64) {
+        self.transferred.uploaded.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub(crate) fn count_downloaded(&self, bytes: u64) {
+        self.transferred.downloaded.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// generates client credentials for password flow b64enc(client id:client secret)
+    fn client_credentials(&self) -> String {
+        let client_credentials = format!("{}:{}", &self.client_id, &self.client_secret);
+
+        base64::encode(client_credentials)
+    }
+    /// convert OAuth2TokenResponse to a connection item
+    fn create_connection(
+        &mut self,
+        token_response: &OAuth2TokenResponse,
+        auth_method: AuthMethod,
+    ) -> &Self {
+        let mut conn = DRACOONConnection::new(token_response, self.clock.as_ref());
+        conn.auth_method = Some(auth_method);
+
+        self.set_session(Some(Arc::new(conn)), true);
+
+        self
+    }
+
+    /// replaces the session of all clones - the metadata cache is cleared, cached
+    /// responses may belong to another user
+    fn set_session(&self, connection: Option<Arc<DRACOONConnection>>, connected: bool) {
+        if let Some(cache) = &self.metadata_cache {
+            cache.clear();
+        }
+
+        let session = Session {
+            connection,
+            connected,
+        };
+
+        match self.session.write() {
+            Ok(mut current) => *current = session,
+            Err(poisoned) => *poisoned.into_inner() = session,
+        }
+    }
+
+    /// current connection of the session (also after disconnect, see Session)
+    fn connection(&self) -> Option<Arc<DRACOONConnection>> {
+        match self.session.read() {
+            Ok(session) => session.connection.clone(),
+            Err(poisoned) => poisoned.into_inner().connection.clone(),
+        }
+    }
+
+    /// checks if the session is connected (connected and not disconnected or closed)
+    fn is_connected(&self) -> bool {
+        match self.session.read() {
+            Ok(session) => session.connected,
+            Err(poisoned) => poisoned.into_inner().connected,
         }
+    }
+
+    /// owned summary of the current connection (None if not connected)
+    pub fn connection_info(&self) -> Option<ConnectionInfo> {
+        self.connection().as_deref().map(DRACOONConnection::info)
+    }
+
+    /// OAuth2 flow of the current connection (None if not connected)
+    pub fn auth_method(&self) -> Option<AuthMethod> {
+        self.connection().and_then(|conn| conn.auth_method)
+    }
+
+    /// checks if a scope was granted for the current connection (e.g. "manage_dl_share")
+    /// without a request - the scope "all" grants every scope, false if not connected
+    pub fn has_scope(&self, scope: &str) -> bool {
+        match self.connection() {
+            Some(conn) => conn
+                .scopes
+                .iter()
+                .any(|granted| granted == scope || granted == SCOPE_ALL),
+            None => false,
+        }
+    }
+
+    fn get_token_url(&self) -> String {
+        self.endpoints.token()
+    }
+
+    /// current time of the client clock (see with_clock)
+    pub(crate) fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    /// endpoint urls of the DRACOON instance (OAuth, API, public API, media server)
+    pub fn endpoints(&self) -> &Endpoints {
+        &self.endpoints
+    }
+
+    /// builds a full API url (base url + API prefix) for the given endpoint path
+    pub(crate) fn api_url(&self, path: &str) -> String {
+        self.endpoints.api(path)
+    }
+
+    /// creates a request to the DRACOON API - PUT, DELETE and PATCH are sent as POST with
+    /// an X-HTTP-Method-Override header if enabled (see with_method_override)
+    pub(crate) fn request(&self, method: Method, url: String) -> RequestBuilder {
+        let tunnel = matches!(method, Method::PUT | Method::DELETE | Method::PATCH);
+
+        match self.method_override && tunnel {
+            true => self
+                .http
+                .post(url)
+                .header(HTTP_METHOD_OVERRIDE_HEADER, method.as_str()),
+            false => self.http.request(method, url),
+        }
+    }
+
+    /// creates a PUT request to the DRACOON API (see request)
+    pub(crate) fn put(&self, url: String) -> RequestBuilder {
+        self.request(Method::PUT, url)
+    }
+
+    /// creates a DELETE request to the DRACOON API (see request)
+    pub(crate) fn delete(&self, url: String) -> RequestBuilder {
+        self.request(Method::DELETE, url)
+    }
+
+    /// builds a full media server url (base url + media server prefix) for the given path
+    pub(crate) fn media_server_url(&self, path: &str) -> String {
+        self.endpoints.media_server(path)
+    }
+
+    /// checks if an absolute url points to the DRACOON instance (same scheme, host and port)
+    pub(crate) fn is_dracoon_url(&self, url: &str) -> bool {
+        match Url::parse(url) {
+            Ok(url) => url.origin() == self.endpoints.base_url().origin(),
+            Err(_) => false,
+        }
+    }
+
+    /// returns the current access token for authenticated requests
+    pub(crate) fn access_token(&self) -> Result<String, DRACOONClientError> {
+        Ok(self.get_connection()?.access_token())
+    }
+
+    /// builds the query params (offset, limit, filter) for paged list endpoints - uses the
+    /// default page size if no limit is given (limits are clamped to the server max)
+    pub(crate) fn list_query(
+        &self,
+        offset: Option<u64>,
+        limit: Option<u64>,
+        filter: Option<&str>,
+    ) -> Vec<(&'static str, String)> {
+        let limit = limit
+            .unwrap_or(self.default_page_size)
+            .clamp(1, DEFAULT_PAGE_SIZE);
+
+        let mut query = vec![("limit", limit.to_string())];
+        if let Some(offset) = offset {
+            query.push(("offset", offset.to_string()));
+        }
+        if let Some(filter) = filter {
+            query.push(("filter", filter.to_string()));
+        }
+
+        query
+    }
+
+    /// parses a JSON API response into the expected model or a DRACOON error
+    pub(crate) async fn parse_response<T: DeserializeOwned>(
+        &self,
+        res: Response,
+    ) -> Result<T, DRACOONClientError> {
+        match res.status() {
+            reqwest::StatusCode::OK | reqwest::StatusCode::CREATED => self.decode_json(res).await,
+            _ => Err(self.parse_error_response(res).await),
+        }
+    }
+
+    /// sends an authenticated GET request through the metadata cache (if enabled) - fresh
+    /// entries are returned without request, stale entries are revalidated via ETag
+    pub(crate) async fn get_cached<T: DeserializeOwned>(
+        &self,
+        req: RequestBuilder,
+    ) -> Result<T, DRACOONClientError> {
+        let cache = match &self.metadata_cache {
+            Some(cache) => cache,
+            None => {
+                let res = self.send_authenticated_idempotent(req).await?;
+                return self.parse_response(res).await;
+            }
+        };
+
+        let key = match req.try_clone().map(|req| req.build()) {
+            Some(Ok(built)) => built.url().to_string(),
+            _ => {
+                let res = self.send_authenticated_idempotent(req).await?;
+                return self.parse_response(res).await;
+            }
+        };
+
+        let (etag, cached_body) = {
+            let entries = cache
+                .entries
+                .lock()
+                .map_err(|_| DRACOONClientError::BrokenConnection)?;
+
+            match entries.get(&key) {
+                Some(entry) if cache.is_fresh(entry, self.now()) => {
+                    return self.decode_body(&entry.body);
+                }
+                Some(entry) => (entry.etag.clone(), Some(entry.body.clone())),
+                None => (None, None),
+            }
+        };
+
+        let req = match &etag {
+            Some(etag) => req.header(IF_NONE_MATCH, etag),
+            None => req,
+        };
+
+        let res = self.send_authenticated_idempotent(req).await?;
+
+        if let (reqwest::StatusCode::NOT_MODIFIED, Some(body)) = (res.status(), &cached_body) {
+            let value = self.decode_body(body)?;
+
+            if let Ok(mut entries) = cache.entries.lock() {
+                if let Some(entry) = entries.get_mut(&key) {
+                    entry.fetched_at = self.now();
+                }
+            }
+
+            return Ok(value);
+        }
+
+        if res.status() != reqwest::StatusCode::OK {
+            return Err(self.parse_error_response(res).await);
+        }
+
+        let etag = res
+            .headers()
+            .get(ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(String::from);
+        let body = read_body(res, self.max_response_body_size).await?;
+        let value = self.decode_body(&body)?;
+
+        if let Ok(mut entries) = cache.entries.lock() {
+            entries.insert(
+                key,
+                CacheEntry {
+                    etag,
+                    body,
+                    fetched_at: self.now(),
+                },
+            );
+        }
+
+        Ok(value)
+    }
+
+    /// clears the metadata cache (see with_metadata_cache), e.g. after changes made by
+    /// other clients
+    pub fn clear_metadata_cache(&self) {
+        if let Some(cache) = &self.metadata_cache {
+            cache.clear();
+        }
+    }
+
+    /// deserializes a JSON body - in debug mode the raw body is kept on failure
+    fn decode_body<T: DeserializeOwned>(&self, body: &str) -> Result<T, DRACOONClientError> {
+        serde_json::from_str::<T>(body).map_err(|source| DRACOONClientError::DecodeError {
+            body: match self.debug_mode {
+                true => body.to_string(),
+                false => String::new(),
+            },
+            source,
+        })
+    }
+
+    /// sends an authenticated list request (GET, idempotent) and returns the page to read
+    /// the items from (see next_list_item)
+    pub(crate) async fn open_list_page<T: DeserializeOwned>(
+        &self,
+        req: RequestBuilder,
+    ) -> Result<ListPage<T>, DRACOONClientError> {
+        let res = self.send_authenticated_idempotent(req).await?;
+
+        if res.status() != reqwest::StatusCode::OK {
+            return Err(self.parse_error_response(res).await);
+        }
+
+        let mut page = ListPage {
+            res: None,
+            splitter: JsonItemsSplitter::new(),
+            items: VecDeque::new(),
+            count: 0,
+        };
+
+        match res.content_length() {
+            Some(length) if length < STREAMED_LIST_MIN_SIZE => {
+                let paged: Paged<T> = self.decode_json(res).await?;
+                page.count = paged.items.len() as u64;
+                page.items.extend(paged.items);
+            }
+            _ => page.res = Some(res),
+        }
+
+        Ok(page)
+    }
+
+    /// returns the next item of a list page (None once the page is read completely)
+    pub(crate) async fn next_list_item<T: DeserializeOwned>(
+        &self,
+        page: &mut ListPage<T>,
+    ) -> Result<Option<T>, DRACOONClientError> {
+        loop {
+            if let Some(item) = page.items.pop_front() {
+                return Ok(Some(item));
+            }
+
+            let res = match &mut page.res {
+                Some(res) => res,
+                None => return Ok(None),
+            };
+
+            match res.chunk().await? {
+                Some(chunk) => {
+                    for item in page.splitter.push(&chunk, self.max_response_body_size)? {
+                        page.items.push_back(self.decode_body(&String::from_utf8_lossy(&item))?);
+                        page.count += 1;
+                    }
+                }
+                None => page.res = None,
+            }
+        }
+    }
+
+    /// deserializes a JSON response body - in debug mode the raw body is kept on failure
+    async fn decode_json<T: DeserializeOwned>(&self, res: Response) -> Result<T, DRACOONClientError> {
+        let body = read_body(res, self.max_response_body_size).await?;
+
+        self.decode_body(&body)
+    }
+
+    /// parses an API response without content (e.g. 204 on delete)
+    pub(crate) async fn parse_empty_response(&self, res: Response) -> Result<(), DRACOONClientError> {
+        match res.status() {
+            status if status.is_success() => Ok(()),
+            _ => Err(self.parse_error_response(res).await),
+        }
+    }
+
+    /// converts an API error response into a client error (403 = missing permission / role)
+    async fn parse_error_response(&self, res: Response) -> DRACOONClientError {
+        let status = res.status();
+
+        let error = match read_error_response(res, self.max_response_body_size).await {
+            Ok(error) => error,
+            Err(e) => return e,
+        };
+
+        match status {
+            reqwest::StatusCode::FORBIDDEN => DRACOONClientError::Forbidden(Box::new(error)),
+            // preconditions the user has to resolve (e.g. set up a key pair or MFA)
+            reqwest::StatusCode::PRECONDITION_FAILED => match error.error_code {
+                Some(ERROR_CODE_KEYPAIR_MISSING) => {
+                    DRACOONClientError::KeypairMissing(Box::new(error))
+                }
+                Some(code) if ERROR_CODES_SECOND_FACTOR_REQUIRED.contains(&code) => {
+                    DRACOONClientError::SecondFactorRequired(Box::new(error))
+                }
+                _ => DRACOONClientError::DRACOONErrror(Box::new(error)),
+            },
+            _ => DRACOONClientError::DRACOONErrror(Box::new(error)),
+        }
+    }
+
+    fn get_connection(&self) -> Result<Arc<DRACOONConnection>, DRACOONClientError> {
+        self.connection().ok_or(DRACOONClientError::BrokenConnection)
+    }
+
+    /// checks if the access token is still valid - considers both the absolute expiry
+    /// (expires_in) and the inactivity expiry (expires_in_inactive since last activity)
+    pub fn check_access_token_validity(&self) -> Result<bool, DRACOONClientError> {
+        let conn = self.get_connection()?;
+
+        Ok(conn.is_valid_at(self.now()))
+    }
+
+    /// refreshes the access token (refresh token flow) if it is expired - a refresh
+    /// running concurrently (e.g. after a 401) is shared instead of repeated
+    pub async fn reconnect_if_needed(&self) -> Result<(), DRACOONClientError> {
+        let conn = self.get_connection()?;
+
+        if conn.is_valid_at(self.now()) {
+            return Ok(());
+        }
+
+        self.refresh_after_unauthorized(&conn, &conn.access_token())
+            .await
+    }
+
+    /// ensures a valid access token (see reconnect_if_needed) and runs the given closure
+    /// with the client - centralizes the "check / refresh, then act" pattern, e.g.
+    /// client.with_valid_token(|client| client.get_node(1)).await
+    /// - only a shared borrow is needed: the tokens are refreshed in place, so the closure
+    ///   can borrow the client and other tasks can keep using it meanwhile
+    /// - the token can still expire while the closure runs: a 401 is refreshed and retried
+    ///   once by every authenticated call anyway
+    pub async fn with_valid_token<'a, F, Fut, T>(&'a self, f: F) -> Result<T, DRACOONClientError>
+    where
+        F: FnOnce(&'a DRACOONClient) -> Fut,
+        Fut: Future<Output = Result<T, DRACOONClientError>>,
+    {
+        self.reconnect_if_needed().await?;
+
+        f(self).await
+    }
+
+    /// authenticated ping
+    pub async fn test_connection(&self) -> Result<bool, DRACOONClientError> {
+        let api_url = self.api_url(DRACOON_AUTHENTICATED_PING);
+
+        let res = self.send_authenticated_idempotent(self.http.get(api_url)).await?;
+
+        match res.status() {
+            reqwest::StatusCode::OK => Ok(true),
+            _ => Ok(false),
+        }
+    }
+
+    /// starts a background task (requires a tokio runtime) that pings DRACOON in the given
+    /// interval to reset the inactivity timer of the session (expires_in_inactive)
+    /// - this only prevents expiry due to inactivity: the access token still expires after
+    ///   its absolute lifetime (expires_in) and must be refreshed
+    /// - the task pings with a clone of the client (shared session): every ping uses the
+    ///   current access token, so refreshes of the client (or a rejected ping) are picked up
+    pub fn start_keepalive(&self, interval: Duration) -> Result<KeepAliveHandle, DRACOONClientError> {
+        self.get_connection()?;
+
+        let client = self.clone();
+
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            // first tick completes immediately - the session is active right now
+            ticker.tick().await;
+
+            loop {
+                ticker.tick().await;
+
+                let _ = client.test_connection().await;
+            }
+        });
+
+        Ok(KeepAliveHandle { task })
+    }
+
+    /// revokes the current access token
+    async fn revoke_access_token(&self) -> Result<(), DRACOONClientError> {
+        let conn = self.get_connection()?;
+
+        let revoke_url = self.endpoints.revoke();
+
+        let revoke_access = OAuth2TokenRevoke {
+            token: conn.access_token(),
+            token_type_hint: TOKEN_TYPE_HINT_ACCESS.to_string(),
+            client_id: self.client_id.clone(),
+            client_secret: self.client_secret.clone(),
+        };
+
+        let res = self
+            .http
+            .post(&revoke_url)
+            .form(&revoke_access)
+            .send()
+            .await?;
+
+        match res.status() {
+            reqwest::StatusCode::OK => Ok(()),
+            _ => Err(DRACOONClientError::BrokenConnection),
+        }
+    }
+
+    /// revokes the access token - the refresh token is kept to allow reconnecting
+    /// (OAuth2ConnectionType::RefreshToken)
+    /// - a client that is not connected (never connected or already disconnected) is
+    ///   returned as is without a revoke request
+    /// - all clones are disconnected (shared session)
+    pub async fn disconnect(self, _revoke_refresh: Option<bool>) -> Result<Self, DRACOONClientError> {
+        if !self.is_connected() {
+            return Ok(self);
+        }
+
+        self.revoke_access_token().await?;
+        self.set_session(self.connection(), false);
+
+        Ok(self)
+    }
+
+    /// canonical cleanup: revokes the access token (if connected) and consumes the client
+    /// - async Drop is not available: call close() before dropping a connected client,
+    ///   otherwise the tokens are not revoked (a warning is logged with the tracing feature)
+    /// - ends the session of all clones
+    pub async fn close(self) -> Result<(), DRACOONClientError> {
+        if self.is_connected() {
+            self.revoke_access_token().await?;
+            self.set_session(self.connection(), false);
+        }
+
+        Ok(())
+    }
+
+    /// main connect method - returns an owned summary of the connection (tokens stay
+    /// encapsulated in the client)
+    /// - returns SecondFactorRequired if the account requires a second factor (see
+    ///   connect_with_second_factor)
+    pub async fn connect(
+        &mut self,
+        connection_type: OAuth2ConnectionType,
+    ) -> Result<ConnectionInfo, DRACOONClientError> {
+        self.connect_with(connection_type, None).await
+    }
+
+    /// completes a login that returned SecondFactorRequired with the one-time password
+    /// (e.g. TOTP app) - the credentials of the flow are sent again with the OTP
+    /// - the second factor only applies to the password flow: the authorization code flow
+    ///   handles it on the DRACOON login page, other flows are connected without it
+    pub async fn connect_with_second_factor(
+        &mut self,
+        connection_type: OAuth2ConnectionType,
+        otp: &str,
+    ) -> Result<ConnectionInfo, DRACOONClientError> {
+        self.connect_with(connection_type, Some(otp.to_string()))
+            .await
+    }
+
+    async fn connect_with(
+        &mut self,
+        connection_type: OAuth2ConnectionType,
+        otp: Option<String>,
+    ) -> Result<ConnectionInfo, DRACOONClientError> {
+        let auth_method = AuthMethod::from(&connection_type);
+
+        let token_response = match connection_type {
+            OAuth2ConnectionType::AuthCode(auth_code) => self.connect_auth_code(auth_code).await,
+            OAuth2ConnectionType::PasswordFlow(user_name, password) => {
+                self.connect_password_flow(user_name, password, otp).await
+            }
+            OAuth2ConnectionType::RefreshToken => self.connect_refresh_token().await,
+        };
+
+        let result = match token_response {
+            Ok(t) => t,
+            Err(e) => return Err(e),
+        };
+
+        self.create_connection(&result, auth_method);
+
+        match self.connection() {
+            Some(c) => Ok(c.info()),
+            None => Err(DRACOONClientError::BrokenConnection),
+        }
+    }
+
+    /// exports the current connection (tokens and expiry) to persist it (None if not
+    /// connected) - see resume_session
+    pub fn export_connection(&self) -> Option<SerializedConnection> {
+        self.connection().as_deref().map(SerializedConnection::from)
+    }
+
+    /// imports a stored connection - the tokens are used as is (see resume_session to
+    /// refresh an expired access token) - replaces the session of all clones
+    pub fn import_connection(&mut self, stored: SerializedConnection) -> ConnectionInfo {
+        let conn = DRACOONConnection::from(stored);
+        let info = conn.info();
+
+        self.set_session(Some(Arc::new(conn)), true);
+
+        info
+    }
+
+    /// creates a client from a stored connection and refreshes the access token if it
+    /// is expired - returns a ready-to-use client (the one call for a daemon on startup)
+    /// - returns ReauthRequired if the refresh token is rejected (expired or revoked):
+    ///   an interactive login (password or authorization code flow) is required then
+    pub async fn resume_session(
+        base_url: &str,
+        client_id: String,
+        client_secret: String,
+        stored: SerializedConnection,
+    ) -> Result<DRACOONClient, DRACOONClientError> {
+        let mut client = DRACOONClient::new(base_url, client_id, client_secret)?;
+        client.import_connection(stored);
+
+        if client.check_access_token_validity()? {
+            return Ok(client);
+        }
+
+        match client.connect(OAuth2ConnectionType::RefreshToken).await {
+            Ok(_) => Ok(client),
+            Err(DRACOONClientError::DRACOONErrror(err))
+                if err.error.as_deref() == Some("invalid_grant")
+                    || matches!(err.code, Some(400) | Some(401)) =>
+            {
+                client.set_session(None, false);
+
+                Err(DRACOONClientError::ReauthRequired)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// parses a token endpoint response - OAuth errors the app registration has to be fixed
+    /// for (grant type not enabled, wrong client credentials) are typed
+    async fn parse_login_response(
+        &self,
+        res: Response,
+        grant_type: &str,
+    ) -> Result<OAuth2TokenResponse, DRACOONClientError> {
+        match res.status() {
+            reqwest::StatusCode::OK => self.decode_json(res).await,
+            _ => {
+                let error = read_error_response(res, self.max_response_body_size).await?;
+
+                match (error.error.as_deref(), error.error_code.or(error.code)) {
+                    (_, Some(code)) if ERROR_CODES_SECOND_FACTOR_REQUIRED.contains(&code) => {
+                        Err(DRACOONClientError::SecondFactorRequired(Box::new(error)))
+                    }
+                    (Some(OAUTH_ERROR_UNSUPPORTED_GRANT_TYPE), _)
+                    | (Some(OAUTH_ERROR_UNAUTHORIZED_CLIENT), _) => {
+                        Err(DRACOONClientError::UnsupportedGrantType {
+                            grant_type: grant_type.to_string(),
+                            hint: format!(
+                                "enable the grant type '{}' for the OAuth app (client id '{}') in the DRACOON OAuth app configuration",
+                                grant_type, self.client_id
+                            ),
+                            error: Box::new(error),
+                        })
+                    }
+                    (Some(OAUTH_ERROR_INVALID_CLIENT), _) => {
+                        Err(DRACOONClientError::InvalidClient(Box::new(error)))
+                    }
+                    _ => Err(DRACOONClientError::DRACOONErrror(Box::new(error))),
+                }
+            }
+        }
+    }
+
+    async fn connect_password_flow(
+        &self,
+        user_name: String,
+        password: String,
+        otp: Option<String>,
+    ) -> Result<OAuth2TokenResponse, DRACOONClientError> {
+ 
+            let client_b64 = self.client_credentials();
+
+            let token_url = self.get_token_url();
+
+            let auth_header = format!("Basic {}", client_b64);
+
+            let auth = OAuth2PasswordFlow {
+                username: user_name,
+                password,
+                grant_type: GRANT_TYPE_PASSWORD.to_string(),
+                otp,
+                scope: self.scope.clone(),
+            };
+
+            let res = self
+                .http
+                .post(token_url)
+                .form(&auth)
+                .header(AUTHORIZATION, auth_header)
+                .send()
+                .await?;
+
+            match self.parse_login_response(res, GRANT_TYPE_PASSWORD).await {
+                Ok(res) => Ok(res),
+                Err(err) => Err(err),
+            }
+      
+    }
+
+    async fn connect_refresh_token(&self) -> Result<OAuth2TokenResponse, DRACOONClientError> {
+        let refresh_token = self.get_connection()?.refresh_token();
 
         let token_url = self.get_token_url();
 
         let auth = OAuth2RefreshTokenFlow {
             client_id: self.client_id.clone(),
             client_secret: self.client_secret.clone(),
-            refresh_token: refresh_token,
+            refresh_token,
             grant_type: GRANT_TYPE_REFRESH_TOKEN.to_string(),
         };
 
-        let res = self.http.post(token_url).form(&auth).send().await?;
+        let res = self
+            .send_with_retry(self.http.post(token_url).form(&auth))
+            .await?;
 
-        match self.parse_login_response(res).await {
+        match self.parse_login_response(res, GRANT_TYPE_REFRESH_TOKEN).await {
             Ok(res) => Ok(res),
             Err(err) => Err(err),
         }
     }
 
-    pub fn get_code_url(&self) -> String {
-        let authorize_url = format!("oauth/authorize?branding=full&response_type=code&client_id={}&redirect_uri={}oauth/callback&scope=all", self.client_id, self.base_url.to_string());
+    /// returns the authorize url for authorization code flow (default parameters, scopes
+    /// of with_scopes if set)
+    pub fn get_code_url(&self) -> Url {
+        let mut options = AuthorizeUrlOptions::default();
+
+        if let Some(scope) = &self.scope {
+            options.scope = scope.clone();
+        }
+
+        self.get_code_url_with(&options)
+    }
 
-        format!("{}{}", &self.base_url.to_string(), authorize_url.as_str())
+    /// returns the authorize url with the given branding / response parameters
+    pub fn get_code_url_with(&self, options: &AuthorizeUrlOptions) -> Url {
+        authorize_url(self.endpoints.base_url(), &self.client_id, options)
     }
 
     pub async fn connect_auth_code(
@@ -350,11 +2667,11 @@ impl DRACOONClient {
                 client_secret: self.client_secret.clone(),
                 code: auth_code,
                 grant_type: GRANT_TYPE_AUTH_CODE.to_string(),
-                redirect_uri: format!("{}{}", self.base_url.to_string(), DRACOON_REDIRECT_URL),
+                redirect_uri: self.endpoints.redirect(),
             };
 
             let res = self.http.post(token_url).form(&auth).send().await?;
-            match self.parse_login_response(res).await {
+            match self.parse_login_response(res, GRANT_TYPE_AUTH_CODE).await {
                 Ok(res) => Ok(res),
                 Err(err) => Err(err),
             }
@@ -0,0 +1,412 @@
+/// required imports
+use crate::core::{DRACOONClient, DRACOONClientError};
+use aes::cipher::{BlockEncrypt, KeyInit};
+use aes::Aes256;
+use pkcs8::der::pem::LineEnding;
+use pkcs8::{DecodePrivateKey, DecodePublicKey, EncodePrivateKey, EncodePublicKey, PrivateKeyInfo};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use rsa::{Oaep, RsaPrivateKey, RsaPublicKey};
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::Sha256;
+use std::convert::TryFrom;
+use zeroize::Zeroizing;
+
+/// constants for API urls
+const DRACOON_USER_KEYPAIR_URL: &str = "user/account/keypair";
+const DRACOON_FILES_URL: &str = "nodes/files";
+const DRACOON_USER_FILE_KEY_URL: &str = "user_file_key";
+
+/// key pair versions (RSA-2048 = "A", RSA-4096 = "RSA-4096")
+const KEYPAIR_VERSION_RSA2048: &str = "A";
+const KEYPAIR_VERSION_RSA4096: &str = "RSA-4096";
+
+/// file key versions (RSA-2048 = "A", RSA-4096 = "RSA-4096/AES-256-GCM")
+const FILE_KEY_VERSION_RSA2048: &str = "A";
+const FILE_KEY_VERSION_RSA4096: &str = "RSA-4096/AES-256-GCM";
+
+/// settings for generated (share) key pairs
+const RSA4096_BITS: usize = 4096;
+const PBKDF2_ITERATIONS: u32 = 1_300_000;
+const PBKDF2_SALT_SIZE: usize = 16;
+const AES_IV_SIZE: usize = 16;
+
+/// file encryption (AES-256-GCM): key size, IV size (96 bit) and block size
+const FILE_KEY_SIZE: usize = 32;
+const FILE_IV_SIZE: usize = 12;
+const GCM_BLOCK_SIZE: usize = 16;
+
+/// GHASH reduction polynomial (GCM bit order)
+const GHASH_R: u128 = 0xE1 << 120;
+
+/// encrypted private key (PKCS#8 PEM encrypted with the key pair password)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivateKeyContainer {
+    pub version: String,
+    pub private_key: String,
+}
+
+/// public key (X.509 PEM)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicKeyContainer {
+    pub version: String,
+    pub public_key: String,
+}
+
+/// key pair of a user or share (client-side encryption)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserKeyPairContainer {
+    pub private_key_container: PrivateKeyContainer,
+    pub public_key_container: PublicKeyContainer,
+}
+
+/// file key encrypted with the public key of a user or share (iv and tag are not
+/// encrypted and stay the same for all recipients)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileKey {
+    pub key: String,
+    pub iv: String,
+    pub tag: Option<String>,
+    pub version: String,
+}
+
+/// decrypted private key of a key pair
+pub struct PlainUserKeyPair {
+    private_key: RsaPrivateKey,
+    version: String,
+}
+
+/// maps crypto errors to a CryptoError with context
+fn crypto_error<E: std::fmt::Display>(context: &str) -> impl FnOnce(E) -> DRACOONClientError + '_ {
+    move |err| DRACOONClientError::CryptoError(format!("{}: {}", context, err))
+}
+
+/// returns the OAEP padding matching a file key version
+fn file_key_padding(version: &str) -> Result<Oaep, DRACOONClientError> {
+    match version {
+        FILE_KEY_VERSION_RSA2048 => Ok(Oaep::new_with_mgf_hash::<Sha256, Sha1>()),
+        FILE_KEY_VERSION_RSA4096 => Ok(Oaep::new::<Sha256>()),
+        version => Err(DRACOONClientError::CryptoError(format!(
+            "unsupported file key version: {}",
+            version
+        ))),
+    }
+}
+
+/// returns the file key version matching a key pair version
+fn file_key_version(keypair_version: &str) -> Result<&'static str, DRACOONClientError> {
+    match keypair_version {
+        KEYPAIR_VERSION_RSA2048 => Ok(FILE_KEY_VERSION_RSA2048),
+        KEYPAIR_VERSION_RSA4096 => Ok(FILE_KEY_VERSION_RSA4096),
+        version => Err(DRACOONClientError::CryptoError(format!(
+            "unsupported key pair version: {}",
+            version
+        ))),
+    }
+}
+
+impl UserKeyPairContainer {
+    /// decrypts the private key with the key pair password
+    pub fn decrypt(&self, password: &str) -> Result<PlainUserKeyPair, DRACOONClientError> {
+        let private_key = RsaPrivateKey::from_pkcs8_encrypted_pem(
+            &self.private_key_container.private_key,
+            password,
+        )
+        .map_err(crypto_error(
+            "failed to decrypt private key (wrong password?)",
+        ))?;
+
+        Ok(PlainUserKeyPair {
+            private_key,
+            version: self.private_key_container.version.clone(),
+        })
+    }
+
+    /// generates a new RSA-4096 key pair - the private key is encrypted with the given
+    /// password (CPU intensive, run it outside of the async executor)
+    pub fn generate(password: &str) -> Result<Self, DRACOONClientError> {
+        let private_key = RsaPrivateKey::new(&mut OsRng, RSA4096_BITS)
+            .map_err(crypto_error("failed to generate key pair"))?;
+
+        let public_key = RsaPublicKey::from(&private_key)
+            .to_public_key_pem(LineEnding::LF)
+            .map_err(crypto_error("failed to encode public key"))?;
+
+        let mut salt = [0u8; PBKDF2_SALT_SIZE];
+        let mut iv = [0u8; AES_IV_SIZE];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut iv);
+
+        let params =
+            pkcs5::pbes2::Parameters::pbkdf2_sha256_aes256cbc(PBKDF2_ITERATIONS, &salt, &iv)
+                .map_err(crypto_error("invalid key encryption parameters"))?;
+
+        let der = private_key
+            .to_pkcs8_der()
+            .map_err(crypto_error("failed to encode private key"))?;
+        let private_key = PrivateKeyInfo::try_from(der.as_bytes())
+            .map_err(crypto_error("failed to encode private key"))?
+            .encrypt_with_params(params, password)
+            .map_err(crypto_error("failed to encrypt private key"))?
+            .to_pem("ENCRYPTED PRIVATE KEY", LineEnding::LF)
+            .map_err(crypto_error("failed to encode private key"))?;
+
+        Ok(UserKeyPairContainer {
+            private_key_container: PrivateKeyContainer {
+                version: KEYPAIR_VERSION_RSA4096.to_string(),
+                private_key: private_key.to_string(),
+            },
+            public_key_container: PublicKeyContainer {
+                version: KEYPAIR_VERSION_RSA4096.to_string(),
+                public_key,
+            },
+        })
+    }
+
+    /// encrypts a plain file key for this key pair (public key)
+    pub fn encrypt_file_key(&self, file_key: &PlainFileKey) -> Result<FileKey, DRACOONClientError> {
+        let public_key = RsaPublicKey::from_public_key_pem(&self.public_key_container.public_key)
+            .map_err(crypto_error("failed to parse public key"))?;
+
+        let version = file_key_version(&self.public_key_container.version)?;
+
+        let key = public_key
+            .encrypt(&mut OsRng, file_key_padding(version)?, &file_key.key)
+            .map_err(crypto_error("failed to encrypt file key"))?;
+
+        Ok(FileKey {
+            key: base64::encode(key),
+            iv: file_key.iv.clone(),
+            tag: file_key.tag.clone(),
+            version: version.to_string(),
+        })
+    }
+}
+
+/// decrypted file key (AES-256 key - iv and tag as returned by DRACOON)
+/// - the key is wiped from memory on drop
+pub struct PlainFileKey {
+    key: Zeroizing<Vec<u8>>,
+    iv: String,
+    tag: Option<String>,
+}
+
+impl PlainFileKey {
+    /// generates a random file key for a new file - the tag is set after the encryption
+    /// (see FileEncryptor::finalize)
+    pub fn generate() -> Self {
+        let mut key = Zeroizing::new(vec![0u8; FILE_KEY_SIZE]);
+        let mut iv = [0u8; FILE_IV_SIZE];
+        OsRng.fill_bytes(&mut key);
+        OsRng.fill_bytes(&mut iv);
+
+        PlainFileKey {
+            key,
+            iv: base64::encode(iv),
+            tag: None,
+        }
+    }
+
+    /// creates a file key from a raw AES-256 key and a 96 bit IV
+    pub fn new(key: Vec<u8>, iv: &[u8]) -> Result<Self, DRACOONClientError> {
+        if key.len() != FILE_KEY_SIZE || iv.len() != FILE_IV_SIZE {
+            return Err(DRACOONClientError::CryptoError(
+                "file key must be 256 bit with a 96 bit IV".to_string(),
+            ));
+        }
+
+        Ok(PlainFileKey {
+            key: Zeroizing::new(key),
+            iv: base64::encode(iv),
+            tag: None,
+        })
+    }
+
+    /// sets the authentication tag of the encrypted file (base64)
+    pub fn set_tag(&mut self, tag: String) {
+        self.tag = Some(tag);
+    }
+}
+
+/// multiplies two blocks in GF(2^128) (GCM bit order, see NIST SP 800-38D)
+fn gf_mul(x: u128, y: u128) -> u128 {
+    let mut z = 0;
+    let mut v = y;
+
+    for i in 0..128 {
+        if (x >> (127 - i)) & 1 == 1 {
+            z ^= v;
+        }
+
+        v = match v & 1 {
+            1 => (v >> 1) ^ GHASH_R,
+            _ => v >> 1,
+        };
+    }
+
+    z
+}
+
+/// streaming AES-256-GCM encryption of a file: the whole file is one GCM message (no
+/// additional data), chunks of any size can be encrypted one after another and the
+/// ciphertext has the same length as the plain text
+/// - CPU intensive for large files, run it outside of the async executor
+pub struct FileEncryptor {
+    cipher: Aes256,
+    iv: [u8; FILE_IV_SIZE],
+    hash_key: u128,
+    counter: u32,
+    keystream: [u8; GCM_BLOCK_SIZE],
+    block: [u8; GCM_BLOCK_SIZE],
+    position: usize,
+    ghash: u128,
+    len: u64,
+}
+
+impl FileEncryptor {
+    /// creates an encryptor for the key and IV of a file key
+    pub fn new(file_key: &PlainFileKey) -> Result<Self, DRACOONClientError> {
+        let cipher =
+            Aes256::new_from_slice(&file_key.key).map_err(crypto_error("invalid file key"))?;
+
+        let iv = base64::decode(&file_key.iv).map_err(crypto_error("invalid file key IV"))?;
+        let iv = <[u8; FILE_IV_SIZE]>::try_from(iv.as_slice()).map_err(|_| {
+            DRACOONClientError::CryptoError("file key IV must be 96 bit".to_string())
+        })?;
+
+        let mut hash_key = [0u8; GCM_BLOCK_SIZE].into();
+        cipher.encrypt_block(&mut hash_key);
+
+        Ok(FileEncryptor {
+            cipher,
+            iv,
+            hash_key: u128::from_be_bytes(hash_key.into()),
+            // counter 1 (J0) is reserved for the tag
+            counter: 1,
+            keystream: [0; GCM_BLOCK_SIZE],
+            block: [0; GCM_BLOCK_SIZE],
+            position: 0,
+            ghash: 0,
+            len: 0,
+        })
+    }
+
+    /// encrypts the next chunk of the file
+    pub fn encrypt(&mut self, chunk: &[u8]) -> Vec<u8> {
+        let mut encrypted = Vec::with_capacity(chunk.len());
+
+        for byte in chunk {
+            if self.position == 0 {
+                self.counter = self.counter.wrapping_add(1);
+                self.keystream = self.counter_block(self.counter);
+            }
+
+            let encrypted_byte = byte ^ self.keystream[self.position];
+            self.block[self.position] = encrypted_byte;
+            encrypted.push(encrypted_byte);
+            self.position += 1;
+
+            if self.position == GCM_BLOCK_SIZE {
+                self.hash_block();
+            }
+        }
+
+        self.len += chunk.len() as u64;
+
+        encrypted
+    }
+
+    /// completes the encryption - returns the authentication tag (base64, see
+    /// PlainFileKey::set_tag)
+    pub fn finalize(mut self) -> String {
+        if self.position > 0 {
+            self.block[self.position..].fill(0);
+            self.hash_block();
+        }
+
+        // lengths in bits: no additional data, ciphertext
+        let lengths = u128::from(self.len) * 8;
+        self.ghash = gf_mul(self.ghash ^ lengths, self.hash_key);
+
+        let tag = self.ghash ^ u128::from_be_bytes(self.counter_block(1));
+
+        base64::encode(tag.to_be_bytes())
+    }
+
+    /// encrypts the counter block IV || counter (keystream)
+    fn counter_block(&self, counter: u32) -> [u8; GCM_BLOCK_SIZE] {
+        let mut block = [0u8; GCM_BLOCK_SIZE];
+        block[..FILE_IV_SIZE].copy_from_slice(&self.iv);
+        block[FILE_IV_SIZE..].copy_from_slice(&counter.to_be_bytes());
+
+        let mut block = block.into();
+        self.cipher.encrypt_block(&mut block);
+
+        block.into()
+    }
+
+    /// adds the current ciphertext block to the GHASH
+    fn hash_block(&mut self) {
+        self.ghash = gf_mul(self.ghash ^ u128::from_be_bytes(self.block), self.hash_key);
+        self.position = 0;
+    }
+}
+
+impl PlainUserKeyPair {
+    /// decrypts a file key encrypted for this key pair
+    pub fn decrypt_file_key(&self, file_key: &FileKey) -> Result<PlainFileKey, DRACOONClientError> {
+        if file_key_version(&self.version)? != file_key.version {
+            return Err(DRACOONClientError::CryptoError(format!(
+                "file key version {} does not match key pair version {}",
+                file_key.version, self.version
+            )));
+        }
+
+        let encrypted = base64::decode(&file_key.key).map_err(crypto_error("invalid file key"))?;
+
+        let key = self
+            .private_key
+            .decrypt(file_key_padding(&file_key.version)?, &encrypted)
+            .map(Zeroizing::new)
+            .map_err(crypto_error("failed to decrypt file key"))?;
+
+        Ok(PlainFileKey {
+            key,
+            iv: file_key.iv.clone(),
+            tag: file_key.tag.clone(),
+        })
+    }
+}
+
+/// client-side encryption implementation for DRACOON client
+impl DRACOONClient {
+    /// fetches the (encrypted) key pair of the current user
+    pub async fn get_user_keypair(&self) -> Result<UserKeyPairContainer, DRACOONClientError> {
+        let api_url = self.api_url(DRACOON_USER_KEYPAIR_URL);
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// fetches the file key of a file in an encrypted room (encrypted for the current user)
+    pub async fn get_user_file_key(&self, node_id: u64) -> Result<FileKey, DRACOONClientError> {
+        let api_url = self.api_url(&format!(
+            "{}/{}/{}",
+            DRACOON_FILES_URL, node_id, DRACOON_USER_FILE_KEY_URL
+        ));
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url))
+            .await?;
+
+        self.parse_response(res).await
+    }
+}
@@ -0,0 +1,471 @@
+/// required imports
+use crate::core::{with_transfer_timeout, DRACOONClient, DRACOONClientError};
+use bytes::Bytes;
+use chrono::offset::Utc;
+use chrono::{DateTime, NaiveDateTime};
+use futures::stream::{self, Stream};
+use reqwest::header::RANGE;
+use reqwest::{RequestBuilder, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::fs::OpenOptions;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio_util::sync::CancellationToken;
+
+/// constants for API urls
+const DRACOON_FILES_URL: &str = "nodes/files";
+const DRACOON_DOWNLOADS_URL: &str = "downloads";
+const DRACOON_MEDIA_IMAGE_URL: &str = "image";
+
+/// query parameters of S3 presigned urls (signing date and lifetime in seconds)
+const S3_PRESIGNED_DATE_PARAM: &str = "X-Amz-Date";
+const S3_PRESIGNED_EXPIRES_PARAM: &str = "X-Amz-Expires";
+const S3_PRESIGNED_DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// suffix of the metadata file kept next to a partial download (see download_resumable)
+const PARTIAL_DOWNLOAD_SUFFIX: &str = ".dracoon-partial";
+
+/// preview sizes (width x height) rendered by the media server
+const THUMBNAIL_SIZE: (u32, u32) = (200, 200);
+const PREVIEW_SIZE: (u32, u32) = (1920, 1080);
+
+/// DRACOON download url response (POST to file downloads url)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadUrl {
+    pub download_url: String,
+}
+
+/// file version a partial download was started for (stored next to the partial file) - a
+/// download only resumes if size and hash of the file in DRACOON are unchanged
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PartialDownload {
+    node_id: u64,
+    size: u64,
+    hash: Option<String>,
+}
+
+/// path of the partial download metadata for a download target
+fn partial_download_path(path: &Path) -> PathBuf {
+    let mut partial_path = path.as_os_str().to_os_string();
+    partial_path.push(PARTIAL_DOWNLOAD_SUFFIX);
+
+    PathBuf::from(partial_path)
+}
+
+/// optional download settings
+#[derive(Debug, Clone, Default)]
+pub struct DownloadOptions {
+    /// aborts the download promptly with a Cancelled error once cancelled
+    pub cancellation_token: Option<CancellationToken>,
+    /// timeout of the content transfer only (the download url is requested with the
+    /// client timeout) - overrides the client timeout, e.g. for a single huge file, and
+    /// returns a Timeout error once exceeded (default: None = client timeout)
+    pub timeout: Option<Duration>,
+}
+
+/// short-lived direct download link of a file (no share object on the server)
+#[derive(Debug, Clone)]
+pub struct DirectDownloadLink {
+    pub url: String,
+    /// expiry of the link: requested lifetime, capped by the actual expiry if the url
+    /// encodes it (S3 presigned urls)
+    pub expires_at: DateTime<Utc>,
+}
+
+/// reads the expiry of an S3 presigned url (X-Amz-Date + X-Amz-Expires)
+fn presigned_url_expiry(url: &str) -> Option<DateTime<Utc>> {
+    let url = reqwest::Url::parse(url).ok()?;
+
+    let mut signed_at = None;
+    let mut expires_in = None;
+
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            S3_PRESIGNED_DATE_PARAM => {
+                signed_at = NaiveDateTime::parse_from_str(&value, S3_PRESIGNED_DATE_FORMAT).ok()
+            }
+            S3_PRESIGNED_EXPIRES_PARAM => expires_in = value.parse::<i64>().ok(),
+            _ => (),
+        }
+    }
+
+    Some(DateTime::<Utc>::from_utc(signed_at?, Utc) + chrono::Duration::seconds(expires_in?))
+}
+
+/// preview kinds rendered by the media server (images and documents)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PreviewKind {
+    Thumbnail,
+    Preview,
+}
+
+impl PreviewKind {
+    /// returns the size (width x height) of the rendered preview
+    pub fn size(&self) -> (u32, u32) {
+        match self {
+            PreviewKind::Thumbnail => THUMBNAIL_SIZE,
+            PreviewKind::Preview => PREVIEW_SIZE,
+        }
+    }
+}
+
+/// download implementation for DRACOON client
+impl DRACOONClient {
+    /// requests a download url for a file
+    pub async fn get_download_url(&self, node_id: u64) -> Result<DownloadUrl, DRACOONClientError> {
+        let api_url = self.api_url(&format!(
+            "{}/{}/{}",
+            DRACOON_FILES_URL, node_id, DRACOON_DOWNLOADS_URL
+        ));
+
+        let res = self.send_authenticated(self.http.post(api_url)).await?;
+
+        self.parse_response(res).await
+    }
+
+    /// returns a media server url for a preview of a file (e.g. to embed it in a UI) - the
+    /// url contains the media token of the file and needs no further authentication
+    /// - returns PreviewUnavailable for folders, rooms and files without media token
+    pub async fn get_preview_url(
+        &self,
+        node_id: u64,
+        kind: PreviewKind,
+    ) -> Result<String, DRACOONClientError> {
+        let node = self.get_node(node_id).await?;
+
+        let media_token = match node.media_token {
+            Some(media_token) if node.is_file() => media_token,
+            _ => return Err(DRACOONClientError::PreviewUnavailable),
+        };
+
+        let (width, height) = kind.size();
+
+        Ok(self.media_server_url(&format!(
+            "{}/{}/{}x{}",
+            DRACOON_MEDIA_IMAGE_URL, media_token, width, height
+        )))
+    }
+
+    /// creates a short-lived direct download link for a file (e.g. to embed it in a web
+    /// page) - unlike a share, no object is created on the server that needs clean-up
+    /// - DRACOON cannot be asked for a lifetime, the ttl is applied client-side: expires_at
+    ///   is now + ttl (client clock) or the earlier actual expiry if the url encodes it -
+    ///   the url itself may stay valid until its server-side expiry
+    pub async fn create_direct_download_link(
+        &self,
+        node_id: u64,
+        ttl: Duration,
+    ) -> Result<DirectDownloadLink, DRACOONClientError> {
+        let download_url = self.get_download_url(node_id).await?;
+
+        // a ttl beyond the representable dates only leaves the server-side expiry
+        let requested = chrono::Duration::from_std(ttl)
+            .ok()
+            .and_then(|ttl| self.now().checked_add_signed(ttl));
+        let expires_at = requested
+            .into_iter()
+            .chain(presigned_url_expiry(&download_url.download_url))
+            .min()
+            .unwrap_or(chrono::MAX_DATETIME);
+
+        Ok(DirectDownloadLink {
+            url: download_url.download_url,
+            expires_at,
+        })
+    }
+
+    /// builds the GET request for a download url - the url is used as is (it may point to
+    /// an S3 / CDN host) and the bearer token is only attached for the DRACOON host itself
+    fn download_request(&self, download_url: &str) -> RequestBuilder {
+        let req = self.http.get(download_url);
+
+        match self.is_dracoon_url(download_url) {
+            true => match self.access_token() {
+                Ok(access_token) => req.bearer_auth(access_token),
+                Err(_) => req,
+            },
+            false => req,
+        }
+    }
+
+    /// fetches the download url and starts the GET request for the file content
+    async fn start_download(&self, node_id: u64) -> Result<Response, DRACOONClientError> {
+        let download_url = self.get_download_url(node_id).await?;
+
+        let res = self
+            .send_limited(self.download_request(&download_url.download_url))
+            .await?;
+
+        Ok(res.error_for_status()?)
+    }
+
+    /// returns the content of a file as a stream of byte chunks (e.g. to pipe it into
+    /// another async consumer) - errors during the transfer are returned as Err items
+    pub async fn download_stream(
+        &self,
+        node_id: u64,
+    ) -> Result<impl Stream<Item = Result<Bytes, DRACOONClientError>> + '_, DRACOONClientError>
+    {
+        let res = self.start_download(node_id).await?;
+
+        Ok(stream::unfold(Some(res), move |res| async move {
+            let mut res = res?;
+
+            match res.chunk().await {
+                Ok(Some(chunk)) => {
+                    self.count_downloaded(chunk.len() as u64);
+                    Some((Ok(chunk), Some(res)))
+                }
+                Ok(None) => None,
+                Err(err) => Some((Err(err.into()), None)),
+            }
+        }))
+    }
+
+    /// streams a file into the given writer - returns the number of bytes written
+    pub async fn download<W: AsyncWrite + Unpin>(
+        &self,
+        node_id: u64,
+        writer: &mut W,
+    ) -> Result<u64, DRACOONClientError> {
+        self.download_cancellable(node_id, writer, &CancellationToken::new())
+            .await
+    }
+
+    /// streams a file into the given writer and aborts promptly with a Cancelled error
+    /// once the token is cancelled - returns the number of bytes written
+    pub async fn download_cancellable<W: AsyncWrite + Unpin>(
+        &self,
+        node_id: u64,
+        writer: &mut W,
+        cancellation_token: &CancellationToken,
+    ) -> Result<u64, DRACOONClientError> {
+        let options = DownloadOptions {
+            cancellation_token: Some(cancellation_token.clone()),
+            ..Default::default()
+        };
+
+        self.download_with(node_id, writer, options).await
+    }
+
+    /// streams a file into the given writer with download options (cancellation, transfer
+    /// timeout) - returns the number of bytes written
+    pub async fn download_with<W: AsyncWrite + Unpin>(
+        &self,
+        node_id: u64,
+        writer: &mut W,
+        options: DownloadOptions,
+    ) -> Result<u64, DRACOONClientError> {
+        let cancellation_token = options.cancellation_token.unwrap_or_default();
+
+        let download_url = tokio::select! {
+            _ = cancellation_token.cancelled() => return Err(DRACOONClientError::Cancelled),
+            download_url = self.get_download_url(node_id) => download_url?,
+        };
+
+        let transfer =
+            self.download_url_to_writer(&download_url.download_url, writer, options.timeout);
+
+        tokio::select! {
+            _ = cancellation_token.cancelled() => Err(DRACOONClientError::Cancelled),
+            res = transfer => res,
+        }
+    }
+
+    /// streams the content of a download url (file or public share) into the given writer
+    /// with an optional transfer timeout - returns the number of bytes written
+    pub(crate) async fn download_url_to_writer<W: AsyncWrite + Unpin>(
+        &self,
+        download_url: &str,
+        writer: &mut W,
+        timeout: Option<Duration>,
+    ) -> Result<u64, DRACOONClientError> {
+        let mut req = self.download_request(download_url);
+
+        if let Some(timeout) = timeout {
+            req = req.timeout(timeout);
+        }
+
+        let transfer = async {
+            let mut res = self.send_limited(req).await?.error_for_status()?;
+            let mut written: u64 = 0;
+
+            while let Some(chunk) = res.chunk().await? {
+                writer.write_all(&chunk).await?;
+                written += chunk.len() as u64;
+                self.count_downloaded(chunk.len() as u64);
+            }
+
+            writer.flush().await?;
+
+            Ok(written)
+        };
+
+        with_transfer_timeout(timeout, transfer).await
+    }
+
+    /// streams a byte range of a file into the given writer - start is inclusive, end is
+    /// inclusive or None (until end of file) - returns the number of bytes written
+    /// - if the server ignores the Range header (200 instead of 206), the bytes before
+    ///   start are skipped and the transfer stops after end
+    pub async fn download_range<W: AsyncWrite + Unpin>(
+        &self,
+        node_id: u64,
+        start: u64,
+        end: Option<u64>,
+        writer: &mut W,
+    ) -> Result<u64, DRACOONClientError> {
+        let range = match end {
+            Some(end) if end < start => return Err(DRACOONClientError::InvalidRange),
+            Some(end) => format!("bytes={}-{}", start, end),
+            None => format!("bytes={}-", start),
+        };
+
+        let download_url = self.get_download_url(node_id).await?;
+
+        let mut res = self
+            .send_limited(
+                self.download_request(&download_url.download_url)
+                    .header(RANGE, range),
+            )
+            .await?
+            .error_for_status()?;
+
+        let mut skip = match res.status() {
+            StatusCode::PARTIAL_CONTENT => 0,
+            _ => start,
+        };
+        let mut remaining = end.map(|end| end - start + 1);
+        let mut written: u64 = 0;
+
+        while let Some(chunk) = res.chunk().await? {
+            self.count_downloaded(chunk.len() as u64);
+
+            let mut data = &chunk[..];
+
+            if skip > 0 {
+                let skipped = skip.min(data.len() as u64);
+                data = &data[skipped as usize..];
+                skip -= skipped;
+            }
+
+            if let Some(left) = remaining {
+                let take = left.min(data.len() as u64);
+                data = &data[..take as usize];
+                remaining = Some(left - take);
+            }
+
+            writer.write_all(data).await?;
+            written += data.len() as u64;
+
+            if remaining == Some(0) {
+                break;
+            }
+        }
+
+        writer.flush().await?;
+
+        Ok(written)
+    }
+
+    /// downloads a file to the given path and resumes from the size of an existing partial
+    /// file (ranged GET, appending) - returns the number of bytes downloaded by this call
+    /// - size and hash of the file are saved next to the partial file (<path>.dracoon-partial,
+    ///   removed once complete) - the download restarts from scratch if they changed
+    ///   server-side, if the metadata is missing or if the partial file is too large
+    /// - returns SizeMismatch if the final size differs from the size reported by DRACOON
+    ///   (a shorter file is resumed by the next call)
+    pub async fn download_resumable(
+        &self,
+        node_id: u64,
+        path: &Path,
+    ) -> Result<u64, DRACOONClientError> {
+        let node = self.fetch_node(node_id).await?;
+        let expected = node.size.unwrap_or(0);
+
+        let partial = PartialDownload {
+            node_id,
+            size: expected,
+            hash: node.hash.filter(|hash| !hash.is_empty()),
+        };
+        let partial_path = partial_download_path(path);
+
+        let saved = match tokio::fs::read(&partial_path).await {
+            Ok(saved) => serde_json::from_slice::<PartialDownload>(&saved).ok(),
+            Err(_) => None,
+        };
+
+        let offset = match tokio::fs::metadata(path).await {
+            Ok(metadata) if saved.as_ref() == Some(&partial) && metadata.len() <= expected => {
+                metadata.len()
+            }
+            _ => 0,
+        };
+
+        if offset == 0 {
+            tokio::fs::write(&partial_path, serde_json::to_vec(&partial)?).await?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(offset > 0)
+            .truncate(offset == 0)
+            .open(path)
+            .await?;
+
+        let written = match offset {
+            offset if offset > 0 && offset == expected => 0,
+            0 => self.download(node_id, &mut file).await?,
+            offset => {
+                self.download_range(node_id, offset, None, &mut file)
+                    .await?
+            }
+        };
+
+        let actual = file.metadata().await?.len();
+
+        if actual != expected {
+            return Err(DRACOONClientError::SizeMismatch { expected, actual });
+        }
+
+        tokio::fs::remove_file(&partial_path).await?;
+
+        Ok(written)
+    }
+
+    /// downloads a (small) file into memory - returns a FileTooLarge error if the file
+    /// exceeds the configured max buffered download size
+    pub async fn download_to_bytes(&self, node_id: u64) -> Result<Vec<u8>, DRACOONClientError> {
+        let mut res = self.start_download(node_id).await?;
+        let max_size = self.max_buffered_download_size;
+
+        if let Some(size) = res.content_length() {
+            if size > max_size {
+                return Err(DRACOONClientError::FileTooLarge);
+            }
+        }
+
+        let mut buffer = Vec::new();
+
+        while let Some(chunk) = res.chunk().await? {
+            if (buffer.len() + chunk.len()) as u64 > max_size {
+                return Err(DRACOONClientError::FileTooLarge);
+            }
+            buffer.extend_from_slice(&chunk);
+            self.count_downloaded(chunk.len() as u64);
+        }
+
+        Ok(buffer)
+    }
+
+    /// downloads a (small) UTF-8 text file into a string (same size cap as download_to_bytes)
+    pub async fn download_to_string(&self, node_id: u64) -> Result<String, DRACOONClientError> {
+        let buffer = self.download_to_bytes(node_id).await?;
+
+        String::from_utf8(buffer).map_err(DRACOONClientError::InvalidUtf8)
+    }
+}
@@ -0,0 +1,132 @@
+/// required imports
+use reqwest::Url;
+
+/// constants for OAuth urls
+const DRACOON_TOKEN_URL: &str = "oauth/token";
+const DRACOON_REDIRECT_URL: &str = "oauth/callback";
+const DRACOON_AUTHORIZE_URL: &str = "oauth/authorize";
+const DRACOON_TOKEN_REVOKE_URL: &str = "oauth/revoke";
+
+/// constants for API urls
+pub(crate) const DRACOON_API_PREFIX: &str = "api/v4";
+const DRACOON_MEDIA_SERVER_PREFIX: &str = "mediaserver";
+const DRACOON_NODES_URL: &str = "nodes";
+const DRACOON_PUBLIC_DOWNLOAD_SHARES_URL: &str = "public/shares/downloads";
+const DRACOON_PUBLIC_UPLOAD_SHARES_URL: &str = "public/shares/uploads";
+
+/// constants for public share links (web app)
+const DRACOON_PUBLIC_DOWNLOAD_LINK: &str = "public/download-shares";
+const DRACOON_PUBLIC_UPLOAD_LINK: &str = "public/upload-shares";
+
+/// endpoint urls of a DRACOON instance - OAuth (base url + oauth/...), API and public API
+/// (base url + api/v4/...) and media server (base url + mediaserver/...)
+/// - computed once from the base url: a trailing slash is added if missing, so urls never
+///   contain double or missing slashes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Endpoints {
+    base_url: Url,
+}
+
+impl Endpoints {
+    pub fn new(base_url: &Url) -> Self {
+        let mut base_url = base_url.clone();
+
+        if !base_url.path().ends_with('/') {
+            let path = format!("{}/", base_url.path());
+            base_url.set_path(&path);
+        }
+
+        Endpoints { base_url }
+    }
+
+    /// base url of the instance (with trailing slash)
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    /// OAuth token url (all grant types)
+    pub fn token(&self) -> String {
+        format!("{}{}", self.base_url, DRACOON_TOKEN_URL)
+    }
+
+    /// OAuth token revocation url
+    pub fn revoke(&self) -> String {
+        format!("{}{}", self.base_url, DRACOON_TOKEN_REVOKE_URL)
+    }
+
+    /// OAuth redirect url of the authorization code flow (DRACOON callback)
+    pub fn redirect(&self) -> String {
+        format!("{}{}", self.base_url, DRACOON_REDIRECT_URL)
+    }
+
+    /// OAuth authorize url (without query parameters, see authorize_url)
+    pub fn authorize(&self) -> Url {
+        let mut url = self.base_url.clone();
+        url.set_path(&format!(
+            "{}{}",
+            self.base_url.path(),
+            DRACOON_AUTHORIZE_URL
+        ));
+        url.set_query(None);
+
+        url
+    }
+
+    /// API url for the given endpoint path (e.g. "user/account") - a leading slash of the
+    /// path is ignored
+    pub fn api(&self, path: &str) -> String {
+        format!(
+            "{}{}/{}",
+            self.base_url,
+            DRACOON_API_PREFIX,
+            path.trim_start_matches('/')
+        )
+    }
+
+    /// media server url for the given path (e.g. previews)
+    pub fn media_server(&self, path: &str) -> String {
+        format!(
+            "{}{}/{}",
+            self.base_url,
+            DRACOON_MEDIA_SERVER_PREFIX,
+            path.trim_start_matches('/')
+        )
+    }
+
+    /// API url of a node (any type)
+    pub fn node(&self, node_id: u64) -> String {
+        self.api(&format!("{}/{}", DRACOON_NODES_URL, node_id))
+    }
+
+    /// public API url of a download share (no authentication required)
+    pub fn public_download_share(&self, access_key: &str) -> String {
+        self.api(&format!(
+            "{}/{}",
+            DRACOON_PUBLIC_DOWNLOAD_SHARES_URL, access_key
+        ))
+    }
+
+    /// public API url of an upload share (no authentication required)
+    pub fn public_upload_share(&self, access_key: &str) -> String {
+        self.api(&format!(
+            "{}/{}",
+            DRACOON_PUBLIC_UPLOAD_SHARES_URL, access_key
+        ))
+    }
+
+    /// public web app link of a download share (for recipients)
+    pub fn public_download_link(&self, access_key: &str) -> String {
+        format!(
+            "{}{}/{}",
+            self.base_url, DRACOON_PUBLIC_DOWNLOAD_LINK, access_key
+        )
+    }
+
+    /// public web app link of an upload share (for recipients)
+    pub fn public_upload_link(&self, access_key: &str) -> String {
+        format!(
+            "{}{}/{}",
+            self.base_url, DRACOON_PUBLIC_UPLOAD_LINK, access_key
+        )
+    }
+}
@@ -0,0 +1,146 @@
+/// required imports
+use crate::core::{DRACOONClient, DRACOONClientError, Paged};
+use chrono::offset::Utc;
+use chrono::{DateTime, SecondsFormat};
+use futures::stream::{self, Stream};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use tokio_util::sync::CancellationToken;
+
+/// constants for API urls
+const DRACOON_EVENTLOG_EVENTS_URL: &str = "eventlog/events";
+
+/// DRACOON event log entry (audit event)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEvent {
+    pub id: u64,
+    pub time: DateTime<Utc>,
+    pub user_id: u64,
+    pub user_name: Option<String>,
+    pub message: Option<String>,
+    pub operation_id: Option<i32>,
+    pub operation_name: Option<String>,
+    /// 0 = success, 2 = failed
+    pub status: i32,
+    pub user_client: Option<String>,
+    pub user_ip: Option<String>,
+    /// name of the (first) affected object
+    #[serde(rename = "objectName1")]
+    pub object_name: Option<String>,
+    #[serde(rename = "objectId1")]
+    pub object_id: Option<u64>,
+}
+
+/// paging state of an audit event stream
+struct AuditEventPages {
+    offset: u64,
+    events: VecDeque<LogEvent>,
+    done: bool,
+}
+
+/// event log implementation for DRACOON client (requires the auditor role)
+impl DRACOONClient {
+    /// fetches audit events in the given date range (paged) - optionally filtered by
+    /// operation type id
+    pub async fn get_audit_events(
+        &self,
+        date_start: DateTime<Utc>,
+        date_end: DateTime<Utc>,
+        operation_type: Option<i32>,
+        offset: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<Paged<LogEvent>, DRACOONClientError> {
+        let api_url = self.api_url(DRACOON_EVENTLOG_EVENTS_URL);
+
+        let mut query = self.list_query(offset, limit, None);
+        query.push((
+            "date_start",
+            date_start.to_rfc3339_opts(SecondsFormat::Secs, true),
+        ));
+        query.push((
+            "date_end",
+            date_end.to_rfc3339_opts(SecondsFormat::Secs, true),
+        ));
+        if let Some(operation_type) = operation_type {
+            query.push(("type", operation_type.to_string()));
+        }
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url).query(&query))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// streams all audit events in the given date range - pages are fetched on demand
+    /// (rate limits are retried after the Retry-After of the server or with backoff); stops
+    /// after the first error
+    pub fn stream_audit_events(
+        &self,
+        date_start: DateTime<Utc>,
+        date_end: DateTime<Utc>,
+    ) -> impl Stream<Item = Result<LogEvent, DRACOONClientError>> + '_ {
+        self.stream_audit_events_cancellable(date_start, date_end, CancellationToken::new())
+    }
+
+    /// streams all audit events in the given date range (see stream_audit_events) - once
+    /// the token is cancelled, a Cancelled error is returned and the stream ends
+    pub fn stream_audit_events_cancellable(
+        &self,
+        date_start: DateTime<Utc>,
+        date_end: DateTime<Utc>,
+        cancellation_token: CancellationToken,
+    ) -> impl Stream<Item = Result<LogEvent, DRACOONClientError>> + '_ {
+        let pages = AuditEventPages {
+            offset: 0,
+            events: VecDeque::new(),
+            done: false,
+        };
+
+        stream::unfold(pages, move |mut pages| {
+            let cancellation_token = cancellation_token.clone();
+
+            async move {
+                loop {
+                    if cancellation_token.is_cancelled() && !pages.done {
+                        pages.done = true;
+                        pages.events.clear();
+                        return Some((Err(DRACOONClientError::Cancelled), pages));
+                    }
+
+                    if let Some(event) = pages.events.pop_front() {
+                        return Some((Ok(event), pages));
+                    }
+
+                    if pages.done {
+                        return None;
+                    }
+
+                    let page = tokio::select! {
+                        _ = cancellation_token.cancelled() => continue,
+                        page = self.get_audit_events(
+                            date_start,
+                            date_end,
+                            None,
+                            Some(pages.offset),
+                            None,
+                        ) => page,
+                    };
+
+                    let page = match page {
+                        Ok(page) => page,
+                        Err(err) => {
+                            pages.done = true;
+                            return Some((Err(err), pages));
+                        }
+                    };
+
+                    pages.offset += page.items.len() as u64;
+                    pages.done = page.items.is_empty() || pages.offset >= page.range.total;
+                    pages.events.extend(page.items);
+                }
+            }
+        })
+    }
+}
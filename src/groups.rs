@@ -0,0 +1,183 @@
+/// required imports
+use crate::core::{DRACOONClient, DRACOONClientError, Expiration, Paged};
+use crate::users::UserInfo;
+use chrono::offset::Utc;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+
+/// constants for API urls
+const DRACOON_GROUPS_URL: &str = "groups";
+const DRACOON_GROUP_USERS_URL: &str = "users";
+
+/// DRACOON group
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Group {
+    pub id: u64,
+    pub name: String,
+    pub cnt_users: Option<u64>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub expire_at: Option<DateTime<Utc>>,
+}
+
+/// group member
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupUser {
+    pub user_info: UserInfo,
+    pub is_member: bool,
+}
+
+/// request to create a group (POST to groups url)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateGroupRequest {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration: Option<Expiration>,
+}
+
+/// request to update a group (PUT to group url) - only set fields are sent
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateGroupRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration: Option<Expiration>,
+}
+
+/// user ids to add to / remove from a group
+#[derive(Debug, Serialize, Deserialize)]
+struct GroupUserIds {
+    ids: Vec<u64>,
+}
+
+/// group management implementation for DRACOON client
+/// (all methods require the user manager role - returns Forbidden otherwise)
+impl DRACOONClient {
+    /// lists groups (paged) - filter uses DRACOON filter syntax (e.g. "name:cn:sales")
+    pub async fn get_groups(
+        &self,
+        offset: Option<u64>,
+        limit: Option<u64>,
+        filter: Option<&str>,
+    ) -> Result<Paged<Group>, DRACOONClientError> {
+        let api_url = self.api_url(DRACOON_GROUPS_URL);
+        let query = self.list_query(offset, limit, filter);
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url).query(&query))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// creates a group with optional expiration date
+    pub async fn create_group(
+        &self,
+        name: &str,
+        expiration: Option<DateTime<Utc>>,
+    ) -> Result<Group, DRACOONClientError> {
+        let api_url = self.api_url(DRACOON_GROUPS_URL);
+
+        let req = CreateGroupRequest {
+            name: name.to_string(),
+            expiration: expiration.map(|date| Expiration::new(Some(date))),
+        };
+
+        let res = self
+            .send_authenticated(self.http.post(api_url).json(&req))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// updates a group by id - returns the updated group
+    pub async fn update_group(
+        &self,
+        group_id: u64,
+        req: &UpdateGroupRequest,
+    ) -> Result<Group, DRACOONClientError> {
+        let api_url = self.api_url(&format!("{}/{}", DRACOON_GROUPS_URL, group_id));
+
+        let res = self
+            .send_authenticated_idempotent(self.put(api_url).json(req))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// deletes a group by id
+    pub async fn delete_group(&self, group_id: u64) -> Result<(), DRACOONClientError> {
+        let api_url = self.api_url(&format!("{}/{}", DRACOON_GROUPS_URL, group_id));
+
+        let res = self
+            .send_authenticated_idempotent(self.delete(api_url))
+            .await?;
+
+        self.parse_empty_response(res).await
+    }
+
+    /// lists the members of a group (paged)
+    pub async fn get_group_users(
+        &self,
+        group_id: u64,
+        offset: Option<u64>,
+        limit: Option<u64>,
+        filter: Option<&str>,
+    ) -> Result<Paged<GroupUser>, DRACOONClientError> {
+        let api_url = self.api_url(&format!(
+            "{}/{}/{}",
+            DRACOON_GROUPS_URL, group_id, DRACOON_GROUP_USERS_URL
+        ));
+        let query = self.list_query(offset, limit, filter);
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url).query(&query))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// adds users to a group - returns the updated group
+    pub async fn add_group_users(
+        &self,
+        group_id: u64,
+        user_ids: Vec<u64>,
+    ) -> Result<Group, DRACOONClientError> {
+        let api_url = self.api_url(&format!(
+            "{}/{}/{}",
+            DRACOON_GROUPS_URL, group_id, DRACOON_GROUP_USERS_URL
+        ));
+
+        let req = GroupUserIds { ids: user_ids };
+
+        let res = self
+            .send_authenticated(self.http.post(api_url).json(&req))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// removes users from a group - returns the updated group
+    pub async fn remove_group_users(
+        &self,
+        group_id: u64,
+        user_ids: Vec<u64>,
+    ) -> Result<Group, DRACOONClientError> {
+        let api_url = self.api_url(&format!(
+            "{}/{}/{}",
+            DRACOON_GROUPS_URL, group_id, DRACOON_GROUP_USERS_URL
+        ));
+
+        let req = GroupUserIds { ids: user_ids };
+
+        let res = self
+            .send_authenticated_idempotent(self.delete(api_url).json(&req))
+            .await?;
+
+        self.parse_response(res).await
+    }
+}
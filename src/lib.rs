@@ -0,0 +1,20 @@
+pub mod account;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod config;
+pub mod core;
+pub mod crypto;
+pub mod downloads;
+pub mod endpoints;
+pub mod eventlog;
+pub mod groups;
+pub mod nodes;
+#[cfg(feature = "persistence")]
+pub mod persistence;
+pub mod profile;
+pub mod recyclebin;
+pub mod shares;
+pub mod subscriptions;
+pub mod uploads;
+pub mod users;
+pub mod webhooks;
@@ -1,27 +1,31 @@
-mod core;
-use reqwest::Url;
-use std::io;
+use dracoon_oxide::core;
 
 #[tokio::main]
 async fn main() {
-    let url = "https://dracoon.team";
-
-    let base_url = Url::parse(url).unwrap();
+    let base_url = "https://dracoon.team";
 
     let client_id = "XXXXXXXXXXXXXXXXXXXXXXXXXX";
     let client_secret = "XXXXXXXXXXXXXXXXXXXXXXXXXX";
 
-    let mut dracoon =
-        core::DRACOONClient::new(base_url, client_id.to_string(), client_secret.to_string());
+    // returns an error for a malformed base url
+    let mut dracoon = match core::DRACOONClient::new(
+        base_url,
+        client_id.to_string(),
+        client_secret.to_string(),
+    ) {
+        Ok(dracoon) => dracoon,
+        Err(e) => {
+            println!("Invalid client configuration: {:?}", e);
+            return;
+        }
+    };
 
     let username = "XXXXXXXXXXXXXXXXXXXXXXXXXX".to_string();
     let password = "XXXXXXXXXXXXXXXXXXXXXXXXXX".to_string(); // or fetch credentials via read_line, see beelow auth code example
 
     // this shows how to authenticate via password flow
     let res = dracoon
-        .connect(
-            core::OAuth2ConnectionType::PasswordFlow(username, password)
-        )
+        .connect(core::OAuth2ConnectionType::PasswordFlow(username, password))
         .await;
 
     println!("{:?}", res);
@@ -46,8 +50,7 @@ async fn main() {
     let conn2 = dracoon.test_connection().await.unwrap();
     println!("Connected: {}", conn2);
 
-
-    /// this shows how to authenticate via authorization code (requires OAuth app to use correct redirect uri and auth code flow!)
+    // this shows how to authenticate via authorization code (requires OAuth app to use correct redirect uri and auth code flow!)
     println!("Get authorization code here: \n {}", dracoon.get_code_url());
     let mut auth_code = String::new();
     std::io::stdin()
@@ -55,13 +58,16 @@ async fn main() {
         .expect("Error parsing user input (auth code).");
 
     let res3 = dracoon
-        .connect(
-            core::OAuth2ConnectionType::AuthCode(auth_code.trim_end().to_string())
-        )
+        .connect(core::OAuth2ConnectionType::AuthCode(
+            auth_code.trim_end().to_string(),
+        ))
         .await;
 
     println!("{:?}", res3);
 
     let conn3 = dracoon.test_connection().await.unwrap();
     println!("Connected: {}", conn3);
+
+    // close the client when done (revokes the access token)
+    dracoon.close().await.unwrap();
 }
@@ -0,0 +1,908 @@
+/// required imports
+use crate::config::ApiVersion;
+use crate::core::{
+    DRACOONClient, DRACOONClientError, DryRunAction, Expiration, ListPage, Paged, DEFAULT_PAGE_SIZE,
+};
+use crate::uploads::ResolutionStrategy;
+use crate::users::UserInfo;
+use chrono::offset::Utc;
+use chrono::DateTime;
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// constants for API urls
+const DRACOON_NODES_URL: &str = "nodes";
+const DRACOON_FOLDERS_URL: &str = "nodes/folders";
+const DRACOON_FILES_URL: &str = "nodes/files";
+const DRACOON_ROOMS_URL: &str = "nodes/rooms";
+const DRACOON_NODES_SEARCH_URL: &str = "nodes/search";
+const DRACOON_MOVE_TO_URL: &str = "move_to";
+const DRACOON_POLICIES_URL: &str = "policies";
+const DRACOON_COPY_TO_URL: &str = "copy_to";
+
+/// minimum API version of the room policies endpoints
+const ROOM_POLICIES_MIN_VERSION: ApiVersion = ApiVersion::new(4, 32, 0);
+
+/// max number of nodes per move / copy request (larger batches are split)
+const NODE_TRANSFER_BATCH_SIZE: usize = 100;
+
+/// max number of nodes per delete request (larger batches are split)
+const NODE_DELETE_BATCH_SIZE: usize = 100;
+
+/// filter for rooms and number of rooms searched at the same time (search_all_rooms)
+const ROOM_FILTER: &str = "type:eq:room";
+const ROOM_SEARCH_CONCURRENCY: usize = 5;
+
+/// DRACOON rule set for node names
+const NODE_NAME_MAX_LENGTH: usize = 150;
+const NODE_NAME_FORBIDDEN_CHARS: [char; 9] = ['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// DRACOON node (room, folder or file)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Node {
+    pub id: u64,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub node_type: NodeType,
+    pub parent_id: Option<u64>,
+    /// path of the parent (e.g. "/Room/Folder/")
+    pub parent_path: Option<String>,
+    /// id of the room the node belongs to (room context, e.g. for search results)
+    pub auth_parent_id: Option<u64>,
+    pub size: Option<u64>,
+    pub is_encrypted: Option<bool>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    /// expired nodes are deleted automatically by DRACOON
+    pub expire_at: Option<DateTime<Utc>>,
+    /// file hash (only files, only if stored by the tenant)
+    #[serde(alias = "fileHash")]
+    pub hash: Option<String>,
+    /// media server token (only files DRACOON can render previews for)
+    pub media_token: Option<String>,
+    /// creator and last editor (None for nodes created by the system)
+    pub created_by: Option<UserInfo>,
+    pub updated_by: Option<UserInfo>,
+}
+
+/// node types - unknown (future) types are kept as Other
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum NodeType {
+    Room,
+    Folder,
+    File,
+    Other(String),
+}
+
+impl From<String> for NodeType {
+    fn from(node_type: String) -> Self {
+        match node_type.as_str() {
+            "room" => NodeType::Room,
+            "folder" => NodeType::Folder,
+            "file" => NodeType::File,
+            _ => NodeType::Other(node_type),
+        }
+    }
+}
+
+impl From<NodeType> for String {
+    fn from(node_type: NodeType) -> Self {
+        match node_type {
+            NodeType::Room => "room".to_string(),
+            NodeType::Folder => "folder".to_string(),
+            NodeType::File => "file".to_string(),
+            NodeType::Other(node_type) => node_type,
+        }
+    }
+}
+
+/// node type predicates
+impl Node {
+    /// checks if the node is a room
+    pub fn is_room(&self) -> bool {
+        self.node_type == NodeType::Room
+    }
+
+    /// checks if the node is a folder
+    pub fn is_folder(&self) -> bool {
+        self.node_type == NodeType::Folder
+    }
+
+    /// checks if the node is a file
+    pub fn is_file(&self) -> bool {
+        self.node_type == NodeType::File
+    }
+
+    /// display name of the creator (e.g. "created by Jane Doe")
+    pub fn created_by_name(&self) -> Option<String> {
+        self.created_by.as_ref().and_then(UserInfo::display_name)
+    }
+
+    /// display name of the last editor (e.g. "modified by Jane Doe")
+    pub fn updated_by_name(&self) -> Option<String> {
+        self.updated_by.as_ref().and_then(UserInfo::display_name)
+    }
+}
+
+/// number of nodes per type in a listing (e.g. "3 folders, 12 files")
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NodeCounts {
+    pub rooms: u64,
+    pub folders: u64,
+    pub files: u64,
+    pub other: u64,
+}
+
+/// node listing helpers
+impl Paged<Node> {
+    /// tallies the nodes of this page by type - DRACOON only returns the total count of
+    /// the listing (range.total), so counts for all pages require the full listing
+    pub fn count_by_type(&self) -> NodeCounts {
+        self.items
+            .iter()
+            .fold(NodeCounts::default(), |mut counts, node| {
+                match node.node_type {
+                    NodeType::Room => counts.rooms += 1,
+                    NodeType::Folder => counts.folders += 1,
+                    NodeType::File => counts.files += 1,
+                    NodeType::Other(_) => counts.other += 1,
+                }
+                counts
+            })
+    }
+}
+
+/// request to create a folder (POST to folders url)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateFolderRequest {
+    pub parent_id: u64,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration: Option<Expiration>,
+}
+
+/// request to update a node (PUT to file / folder url) - only set fields are sent
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateNodeRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration: Option<Expiration>,
+}
+
+impl UpdateNodeRequest {
+    /// request to set (Some) or remove (None) the expiration date of a node
+    pub fn expiration(expire_at: Option<DateTime<Utc>>) -> Self {
+        UpdateNodeRequest {
+            expiration: Some(Expiration::new(expire_at)),
+            ..Default::default()
+        }
+    }
+}
+
+/// room policies (compliance settings of a room)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomPolicies {
+    /// default expiration period of new nodes in seconds (0 = no expiration)
+    pub default_expiration_period: Option<i64>,
+    pub is_virus_protection_enabled: Option<bool>,
+    /// default classification of new files (1 = public ... 4 = strictly confidential)
+    pub default_classification: Option<i32>,
+}
+
+/// request to update room policies (PUT to room policies url) - only set fields are sent
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRoomPoliciesRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_expiration_period: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_virus_protection_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_classification: Option<i32>,
+}
+
+/// node to move or copy
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NodeTransferItem {
+    pub id: u64,
+}
+
+/// request to move / copy nodes into a target parent (POST to move_to / copy_to url)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeTransferRequest {
+    pub items: Vec<NodeTransferItem>,
+    pub resolution_strategy: ResolutionStrategy,
+}
+
+/// request to delete nodes (DELETE to nodes url)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeleteNodesRequest {
+    node_ids: Vec<u64>,
+}
+
+/// state of a node listing stream (see stream_nodes)
+struct NodePages {
+    offset: u64,
+    page: Option<ListPage<Node>>,
+    done: bool,
+}
+
+/// validates a node name against the DRACOON rule set - returns InvalidNodeName
+/// with a description of the violated rule
+pub fn validate_node_name(name: &str) -> Result<(), DRACOONClientError> {
+    let invalid = |reason: &str| Err(DRACOONClientError::InvalidNodeName(reason.to_string()));
+
+    if name.is_empty() {
+        return invalid("name must not be empty");
+    }
+
+    if name.chars().count() > NODE_NAME_MAX_LENGTH {
+        return invalid("name must not exceed 150 characters");
+    }
+
+    if name == "." || name == ".." {
+        return invalid("name must not be '.' or '..'");
+    }
+
+    if name.trim() != name {
+        return invalid("name must not start or end with whitespace");
+    }
+
+    if name.chars().any(|c| NODE_NAME_FORBIDDEN_CHARS.contains(&c)) {
+        return invalid("name must not contain any of < > : \" / \\ | ? *");
+    }
+
+    if name.chars().any(char::is_control) {
+        return invalid("name must not contain control characters");
+    }
+
+    Ok(())
+}
+
+/// node implementation for DRACOON client
+impl DRACOONClient {
+    /// fetches a node (room, folder or file) by id - served from the metadata cache while
+    /// the entry is fresh (see fetch_node for the current server state)
+    pub async fn get_node(&self, node_id: u64) -> Result<Node, DRACOONClientError> {
+        let api_url = self.endpoints().node(node_id);
+
+        self.get_cached(self.http.get(api_url)).await
+    }
+
+    /// fetches the current state of a node, bypassing the metadata cache - used by guards
+    /// and updates that must not act on a stale node
+    pub async fn fetch_node(&self, node_id: u64) -> Result<Node, DRACOONClientError> {
+        let api_url = self.endpoints().node(node_id);
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// lists the child nodes of a parent (0 = root) - filter uses DRACOON filter syntax
+    /// (e.g. "name:cn:report")
+    pub async fn get_nodes(
+        &self,
+        parent_id: u64,
+        offset: Option<u64>,
+        limit: Option<u64>,
+        filter: Option<&str>,
+    ) -> Result<Paged<Node>, DRACOONClientError> {
+        let api_url = self.api_url(DRACOON_NODES_URL);
+
+        let mut query = self.list_query(offset, limit, filter);
+        query.push(("parent_id", parent_id.to_string()));
+
+        self.get_cached(self.http.get(api_url).query(&query)).await
+    }
+
+    /// streams all child nodes of a parent (0 = root) - pages are fetched on demand and
+    /// large pages are deserialized node by node while they are received, so memory stays
+    /// flat for huge rooms (not cached, see with_metadata_cache); stops after the first error
+    pub fn stream_nodes<'a>(
+        &'a self,
+        parent_id: u64,
+        filter: Option<&'a str>,
+    ) -> impl Stream<Item = Result<Node, DRACOONClientError>> + 'a {
+        let pages = NodePages {
+            offset: 0,
+            page: None,
+            done: false,
+        };
+
+        stream::unfold(pages, move |mut pages| async move {
+            loop {
+                if pages.done {
+                    return None;
+                }
+
+                let page = match &mut pages.page {
+                    Some(page) => page,
+                    None => {
+                        let api_url = self.api_url(DRACOON_NODES_URL);
+
+                        let mut query =
+                            self.list_query(Some(pages.offset), Some(DEFAULT_PAGE_SIZE), filter);
+                        query.push(("parent_id", parent_id.to_string()));
+
+                        match self
+                            .open_list_page(self.http.get(api_url).query(&query))
+                            .await
+                        {
+                            Ok(page) => pages.page.insert(page),
+                            Err(err) => {
+                                pages.done = true;
+                                return Some((Err(err), pages));
+                            }
+                        }
+                    }
+                };
+
+                match self.next_list_item(page).await {
+                    Ok(Some(node)) => return Some((Ok(node), pages)),
+                    Ok(None) => {
+                        pages.done = page.count() < DEFAULT_PAGE_SIZE;
+                        pages.offset += page.count();
+                        pages.page = None;
+                    }
+                    Err(err) => {
+                        pages.done = true;
+                        return Some((Err(err), pages));
+                    }
+                }
+            }
+        })
+    }
+
+    /// lists the rooms at the root level (paged)
+    pub async fn get_rooms(
+        &self,
+        offset: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<Paged<Node>, DRACOONClientError> {
+        self.get_nodes(0, offset, limit, Some(ROOM_FILTER)).await
+    }
+
+    /// searches nodes by name below a parent (0 = all accessible nodes) including all
+    /// sub levels (paged) - filter uses DRACOON filter syntax (e.g. "type:eq:file")
+    pub async fn search_nodes(
+        &self,
+        search_string: &str,
+        parent_id: u64,
+        offset: Option<u64>,
+        limit: Option<u64>,
+        filter: Option<&str>,
+    ) -> Result<Paged<Node>, DRACOONClientError> {
+        let api_url = self.api_url(DRACOON_NODES_SEARCH_URL);
+
+        let mut query = self.list_query(offset, limit, filter);
+        query.push(("search_string", search_string.to_string()));
+        query.push(("parent_id", parent_id.to_string()));
+        query.push(("depth_level", "-1".to_string()));
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url).query(&query))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// searches all accessible rooms one by one (bounded concurrency) - fallback for
+    /// tenants restricting global search; results are de-duplicated by node id and carry
+    /// the containing room in auth_parent_id
+    pub async fn search_all_rooms(&self, query: &str) -> Result<Vec<Node>, DRACOONClientError> {
+        let mut rooms = Vec::new();
+        loop {
+            let page = self.get_rooms(Some(rooms.len() as u64), None).await?;
+            let total = page.range.total;
+            let empty = page.items.is_empty();
+            rooms.extend(page.items);
+
+            if empty || rooms.len() as u64 >= total {
+                break;
+            }
+        }
+
+        let results: Vec<Vec<Node>> = stream::iter(rooms)
+            .map(|room| self.search_room(query, room))
+            .buffer_unordered(ROOM_SEARCH_CONCURRENCY)
+            .try_collect()
+            .await?;
+
+        let mut seen = HashSet::new();
+
+        Ok(results
+            .into_iter()
+            .flatten()
+            .filter(|node| seen.insert(node.id))
+            .collect())
+    }
+
+    /// fetches all search results within a room (all pages)
+    async fn search_room(&self, query: &str, room: Node) -> Result<Vec<Node>, DRACOONClientError> {
+        let mut nodes = Vec::new();
+        loop {
+            let page = self
+                .search_nodes(query, room.id, Some(nodes.len() as u64), None, None)
+                .await?;
+            let total = page.range.total;
+            let empty = page.items.is_empty();
+            nodes.extend(page.items.into_iter().map(|mut node| {
+                node.auth_parent_id.get_or_insert(room.id);
+                node
+            }));
+
+            if empty || nodes.len() as u64 >= total {
+                break;
+            }
+        }
+
+        Ok(nodes)
+    }
+
+    /// creates a folder in the given parent (room or folder)
+    pub async fn create_folder(
+        &self,
+        parent_id: u64,
+        name: &str,
+    ) -> Result<Node, DRACOONClientError> {
+        self.create_folder_with_expiration(parent_id, name, None)
+            .await
+    }
+
+    /// creates a folder which is deleted automatically after the given date (None = no
+    /// expiration)
+    pub async fn create_folder_with_expiration(
+        &self,
+        parent_id: u64,
+        name: &str,
+        expire_at: Option<DateTime<Utc>>,
+    ) -> Result<Node, DRACOONClientError> {
+        validate_node_name(name)?;
+
+        let api_url = self.api_url(DRACOON_FOLDERS_URL);
+
+        let req = CreateFolderRequest {
+            parent_id,
+            name: name.to_string(),
+            notes: None,
+            expiration: expire_at.map(|date| Expiration::new(Some(date))),
+        };
+
+        let res = self
+            .send_authenticated(self.http.post(api_url).json(&req))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// creates a folder if absent or returns the existing folder with the same name
+    /// - a conflict (409) on creation is treated as success (e.g. concurrent creation): all
+    ///   pages of the parent are searched (uncached) for a folder with exactly this name
+    pub async fn ensure_folder(
+        &self,
+        parent_id: u64,
+        name: &str,
+    ) -> Result<Node, DRACOONClientError> {
+        match self.create_folder(parent_id, name).await {
+            Err(DRACOONClientError::DRACOONErrror(err)) if err.code == Some(409) => {
+                let filter = format!("name:eq:{}", name);
+                let mut nodes = Box::pin(self.stream_nodes(parent_id, Some(&filter)));
+
+                while let Some(node) = nodes.try_next().await? {
+                    if node.name == name && node.is_folder() {
+                        return Ok(node);
+                    }
+                }
+
+                Err(DRACOONClientError::DRACOONErrror(err))
+            }
+            res => res,
+        }
+    }
+
+    /// returns the hash of a file stored by DRACOON - None if the tenant does not store
+    /// hashes (fallback: download and hash the file)
+    pub async fn get_file_hash(&self, node_id: u64) -> Result<Option<String>, DRACOONClientError> {
+        let node = self.fetch_node(node_id).await?;
+
+        Ok(node.hash.filter(|hash| !hash.is_empty()))
+    }
+
+    /// returns the full path of a node (e.g. "/Room/Folder/file.txt") - uses the
+    /// parentPath returned by DRACOON or walks the parents if it is missing
+    pub async fn get_node_path(&self, node_id: u64) -> Result<String, DRACOONClientError> {
+        let node = self.get_node(node_id).await?;
+
+        if let Some(parent_path) = &node.parent_path {
+            return Ok(format!(
+                "{}/{}",
+                parent_path.trim_end_matches('/'),
+                node.name
+            ));
+        }
+
+        let mut names = vec![node.name];
+        let mut parent_id = node.parent_id;
+
+        while let Some(id) = parent_id {
+            let parent = self.get_node(id).await?;
+            names.push(parent.name);
+            parent_id = parent.parent_id;
+        }
+
+        names.reverse();
+
+        Ok(format!("/{}", names.join("/")))
+    }
+
+    /// updates a node (file, folder or room) by id - returns the updated node
+    /// (fetches the node first to pick the endpoint matching its type)
+    pub async fn update_node(
+        &self,
+        node_id: u64,
+        req: &UpdateNodeRequest,
+    ) -> Result<Node, DRACOONClientError> {
+        let api_url = self.node_update_url(node_id).await?;
+
+        let res = self
+            .send_authenticated_idempotent(self.put(api_url).json(req))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// updates a node with a raw JSON object (only the given fields are sent) - for
+    /// fields not (yet) modelled by UpdateNodeRequest, returns the updated node
+    /// - returns InvalidRequestBody if the body is not a JSON object
+    pub async fn patch_node(
+        &self,
+        node_id: u64,
+        body: serde_json::Value,
+    ) -> Result<Node, DRACOONClientError> {
+        if !body.is_object() {
+            return Err(DRACOONClientError::InvalidRequestBody(format!(
+                "expected a JSON object, got: {}",
+                body
+            )));
+        }
+
+        let api_url = self.node_update_url(node_id).await?;
+
+        let res = self
+            .send_authenticated_idempotent(self.put(api_url).json(&body))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// update url of a node (fetches the node first to pick the endpoint matching its type)
+    async fn node_update_url(&self, node_id: u64) -> Result<String, DRACOONClientError> {
+        let node = self.fetch_node(node_id).await?;
+
+        let url = match node.node_type {
+            NodeType::File => DRACOON_FILES_URL,
+            NodeType::Folder => DRACOON_FOLDERS_URL,
+            NodeType::Room => DRACOON_ROOMS_URL,
+            NodeType::Other(_) => return Err(DRACOONClientError::MissingArguments),
+        };
+
+        Ok(self.api_url(&format!("{}/{}", url, node_id)))
+    }
+
+    /// sets (Some) or removes (None) the expiration date of a file or folder - expired
+    /// nodes are deleted automatically by DRACOON
+    pub async fn set_node_expiration(
+        &self,
+        node_id: u64,
+        expire_at: Option<DateTime<Utc>>,
+    ) -> Result<Node, DRACOONClientError> {
+        self.update_node(node_id, &UpdateNodeRequest::expiration(expire_at))
+            .await
+    }
+
+    /// fetches the policies of a room (default expiration, virus protection, classification)
+    /// - returns FeatureUnavailable on servers before 4.32
+    pub async fn get_room_policies(
+        &self,
+        room_id: u64,
+    ) -> Result<RoomPolicies, DRACOONClientError> {
+        self.requires_version(ROOM_POLICIES_MIN_VERSION).await?;
+
+        let api_url = self.api_url(&format!(
+            "{}/{}/{}",
+            DRACOON_ROOMS_URL, room_id, DRACOON_POLICIES_URL
+        ));
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// updates the policies of a room (requires room admin permissions, servers from 4.32)
+    pub async fn update_room_policies(
+        &self,
+        room_id: u64,
+        req: &UpdateRoomPoliciesRequest,
+    ) -> Result<(), DRACOONClientError> {
+        self.requires_version(ROOM_POLICIES_MIN_VERSION).await?;
+
+        let api_url = self.api_url(&format!(
+            "{}/{}/{}",
+            DRACOON_ROOMS_URL, room_id, DRACOON_POLICIES_URL
+        ));
+
+        let res = self
+            .send_authenticated_idempotent(self.put(api_url).json(req))
+            .await?;
+
+        self.parse_empty_response(res).await
+    }
+
+    /// moves nodes into a target parent (room or folder) - returns the target node
+    pub async fn move_nodes(
+        &self,
+        target_parent_id: u64,
+        node_ids: &[u64],
+        resolution_strategy: ResolutionStrategy,
+    ) -> Result<Node, DRACOONClientError> {
+        self.transfer_nodes(
+            DRACOON_MOVE_TO_URL,
+            target_parent_id,
+            node_ids,
+            resolution_strategy,
+        )
+        .await
+    }
+
+    /// copies nodes into a target parent (room or folder) - returns the target node
+    /// (skipped in dry run mode if existing nodes are overwritten)
+    pub async fn copy_nodes(
+        &self,
+        target_parent_id: u64,
+        node_ids: &[u64],
+        resolution_strategy: ResolutionStrategy,
+    ) -> Result<Node, DRACOONClientError> {
+        self.transfer_nodes(
+            DRACOON_COPY_TO_URL,
+            target_parent_id,
+            node_ids,
+            resolution_strategy,
+        )
+        .await
+    }
+
+    /// moves nodes in batches and reports the result per node id (see transfer_nodes_each)
+    pub async fn move_nodes_each(
+        &self,
+        target_parent_id: u64,
+        node_ids: &[u64],
+        resolution_strategy: ResolutionStrategy,
+    ) -> Vec<(u64, Result<(), DRACOONClientError>)> {
+        self.transfer_nodes_each(
+            DRACOON_MOVE_TO_URL,
+            target_parent_id,
+            node_ids,
+            resolution_strategy,
+        )
+        .await
+    }
+
+    /// copies nodes in batches and reports the result per node id (see transfer_nodes_each)
+    pub async fn copy_nodes_each(
+        &self,
+        target_parent_id: u64,
+        node_ids: &[u64],
+        resolution_strategy: ResolutionStrategy,
+    ) -> Vec<(u64, Result<(), DRACOONClientError>)> {
+        self.transfer_nodes_each(
+            DRACOON_COPY_TO_URL,
+            target_parent_id,
+            node_ids,
+            resolution_strategy,
+        )
+        .await
+    }
+
+    async fn transfer_nodes(
+        &self,
+        transfer_url: &str,
+        target_parent_id: u64,
+        node_ids: &[u64],
+        resolution_strategy: ResolutionStrategy,
+    ) -> Result<Node, DRACOONClientError> {
+        if transfer_url == DRACOON_MOVE_TO_URL {
+            self.check_dry_run(|| DryRunAction::MoveNodes {
+                target_parent_id,
+                node_ids: node_ids.to_vec(),
+            })?;
+        } else if resolution_strategy == ResolutionStrategy::Overwrite {
+            // copies only destroy data if they replace existing nodes
+            self.check_dry_run(|| DryRunAction::OverwriteCopy {
+                target_parent_id,
+                node_ids: node_ids.to_vec(),
+            })?;
+        }
+
+        let api_url = self.api_url(&format!(
+            "{}/{}/{}",
+            DRACOON_NODES_URL, target_parent_id, transfer_url
+        ));
+
+        let req = NodeTransferRequest {
+            items: node_ids
+                .iter()
+                .map(|id| NodeTransferItem { id: *id })
+                .collect(),
+            resolution_strategy,
+        };
+
+        let res = self
+            .send_authenticated(self.http.post(api_url).json(&req))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// splits the nodes into batches - if a batch fails, its nodes are transferred one by
+    /// one to report which node ids failed (e.g. missing permissions on single nodes)
+    /// - note: this relies on DRACOON rejecting a failed batch as a whole; use the Fail
+    ///   resolution strategy to rule out duplicates when copying
+    async fn transfer_nodes_each(
+        &self,
+        transfer_url: &str,
+        target_parent_id: u64,
+        node_ids: &[u64],
+        resolution_strategy: ResolutionStrategy,
+    ) -> Vec<(u64, Result<(), DRACOONClientError>)> {
+        let mut results = Vec::with_capacity(node_ids.len());
+
+        for batch in node_ids.chunks(NODE_TRANSFER_BATCH_SIZE) {
+            let batch_result = self
+                .transfer_nodes(transfer_url, target_parent_id, batch, resolution_strategy)
+                .await;
+
+            match batch_result {
+                Ok(_) => results.extend(batch.iter().map(|id| (*id, Ok(())))),
+                Err(err) if batch.len() == 1 => results.push((batch[0], Err(err))),
+                Err(_) => {
+                    for id in batch {
+                        let res = self
+                            .transfer_nodes(
+                                transfer_url,
+                                target_parent_id,
+                                &[*id],
+                                resolution_strategy,
+                            )
+                            .await
+                            .map(|_| ());
+                        results.push((*id, res));
+                    }
+                }
+            }
+        }
+
+        results
+    }
+
+    /// deletes a node (room, folder or file) by id
+    pub async fn delete_node(&self, node_id: u64) -> Result<(), DRACOONClientError> {
+        self.check_dry_run(|| DryRunAction::DeleteNode { node_id })?;
+
+        let api_url = self.endpoints().node(node_id);
+
+        let res = self
+            .send_authenticated_idempotent(self.delete(api_url))
+            .await?;
+
+        self.parse_empty_response(res).await
+    }
+
+    /// deletes all child nodes of a room (the room itself is kept) - returns the number of
+    /// deleted nodes (see empty_room_each for the failed node ids)
+    pub async fn empty_room(&self, room_id: u64) -> Result<usize, DRACOONClientError> {
+        let results = self.empty_room_each(room_id).await?;
+
+        Ok(results.iter().filter(|(_, res)| res.is_ok()).count())
+    }
+
+    /// deletes all child nodes of a room in batches and reports the result per node id
+    /// - only rooms can be emptied (MissingArguments otherwise, e.g. for the root 0)
+    /// - all pages are listed uncached before deleting (deleting shifts the offsets of the
+    ///   following pages)
+    /// - if a batch fails, its nodes are deleted one by one so the other nodes are still
+    ///   deleted (e.g. missing permissions on single nodes)
+    /// - in dry run mode the children are listed and returned in the EmptyRoom action
+    pub async fn empty_room_each(
+        &self,
+        room_id: u64,
+    ) -> Result<Vec<(u64, Result<(), DRACOONClientError>)>, DRACOONClientError> {
+        if !self.fetch_node(room_id).await?.is_room() {
+            return Err(DRACOONClientError::MissingArguments);
+        }
+
+        let nodes: Vec<Node> = self.stream_nodes(room_id, None).try_collect().await?;
+        let node_ids: Vec<u64> = nodes.iter().map(|node| node.id).collect();
+
+        self.check_dry_run(|| DryRunAction::EmptyRoom {
+            room_id,
+            node_ids: node_ids.clone(),
+        })?;
+
+        let mut results = Vec::with_capacity(node_ids.len());
+
+        for batch in node_ids.chunks(NODE_DELETE_BATCH_SIZE) {
+            match self.delete_nodes(batch).await {
+                Ok(()) => results.extend(batch.iter().map(|id| (*id, Ok(())))),
+                Err(err) if batch.len() == 1 => results.push((batch[0], Err(err))),
+                Err(_) => {
+                    for id in batch {
+                        let res = self.delete_node(*id).await;
+                        results.push((*id, res));
+                    }
+                }
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// deletes a batch of nodes with a single request
+    async fn delete_nodes(&self, node_ids: &[u64]) -> Result<(), DRACOONClientError> {
+        let api_url = self.api_url(DRACOON_NODES_URL);
+
+        let req = DeleteNodesRequest {
+            node_ids: node_ids.to_vec(),
+        };
+
+        let res = self
+            .send_authenticated_idempotent(self.delete(api_url).json(&req))
+            .await?;
+
+        self.parse_empty_response(res).await
+    }
+
+    /// guard for destructive operations: fetches the node and returns it only if
+    /// its updatedAt still matches the expected timestamp, otherwise returns a Conflict error
+    pub async fn ensure_node_unchanged(
+        &self,
+        node_id: u64,
+        expected_updated_at: DateTime<Utc>,
+    ) -> Result<Node, DRACOONClientError> {
+        let node = self.fetch_node(node_id).await?;
+
+        match node.updated_at {
+            Some(updated_at) if updated_at == expected_updated_at => Ok(node),
+            _ => Err(DRACOONClientError::Conflict),
+        }
+    }
+
+    /// deletes a node only if it has not been modified since the expected timestamp
+    pub async fn delete_node_if_unchanged(
+        &self,
+        node_id: u64,
+        expected_updated_at: DateTime<Utc>,
+    ) -> Result<(), DRACOONClientError> {
+        self.ensure_node_unchanged(node_id, expected_updated_at)
+            .await?;
+
+        self.delete_node(node_id).await
+    }
+}
@@ -0,0 +1,130 @@
+/// required imports
+use crate::core::{masked, DRACOONClient, DRACOONClientError, SerializedConnection};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+use std::time::Duration;
+
+/// snapshot of a whole client (settings and session) to restore it later, e.g. after a
+/// restart of a tool - see DRACOONClient::export_state and DRACOONClient::from_state
+/// - the connection contains the tokens and the client secret is only included on
+///   request (export_state(true)): store the state like a password (masked in Debug)
+/// - not included: shared reqwest clients, certificates, callbacks, clock, cache and
+///   dry run settings (apply them again via DRACOONClient::builder if needed)
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientState {
+    pub base_url: String,
+    pub client_id: String,
+    /// sensitive - only set if exported with include_client_secret
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_idle_timeout_ms: Option<u64>,
+    pub max_retries: u32,
+    pub max_concurrent_requests: usize,
+    pub default_page_size: u64,
+    pub max_response_body_size: u64,
+    pub method_override: bool,
+    /// tokens of the session (None if not connected)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection: Option<SerializedConnection>,
+}
+
+impl fmt::Debug for ClientState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientState")
+            .field("base_url", &self.base_url)
+            .field("client_id", &self.client_id)
+            .field("client_secret", &masked(&self.client_secret))
+            .field("scopes", &self.scopes)
+            .field("timeout_ms", &self.timeout_ms)
+            .field("pool_idle_timeout_ms", &self.pool_idle_timeout_ms)
+            .field("max_retries", &self.max_retries)
+            .field("max_concurrent_requests", &self.max_concurrent_requests)
+            .field("default_page_size", &self.default_page_size)
+            .field("max_response_body_size", &self.max_response_body_size)
+            .field("method_override", &self.method_override)
+            .field("connection", &self.connection)
+            .finish()
+    }
+}
+
+/// converts a duration to milliseconds (saturating)
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// persistence implementation for DRACOON client (persistence feature)
+impl DRACOONClient {
+    /// exports the settings and the session of the client - the client secret is only
+    /// included if include_client_secret is set (sensitive, see ClientState)
+    pub fn export_state(&self, include_client_secret: bool) -> ClientState {
+        let config = self.config();
+
+        ClientState {
+            base_url: config.base_url.to_string(),
+            client_id: config.client_id.clone(),
+            client_secret: match include_client_secret {
+                true => Some(self.client_secret.clone()),
+                false => None,
+            },
+            scopes: self
+                .scope
+                .as_ref()
+                .map(|scope| scope.split(' ').map(String::from).collect()),
+            timeout_ms: config.timeout.map(millis),
+            pool_idle_timeout_ms: config.pool_idle_timeout.map(millis),
+            max_retries: config.max_retries,
+            max_concurrent_requests: config.max_concurrent_requests,
+            default_page_size: config.default_page_size,
+            max_response_body_size: config.max_response_body_size,
+            method_override: config.method_override,
+            connection: self.export_connection(),
+        }
+    }
+
+    /// rebuilds a client from an exported state - the client secret of the state is used
+    /// unless one is passed (MissingArguments if neither is set), the connection is imported
+    /// as is (see reconnect_if_needed to refresh an expired access token)
+    /// - returns InvalidUrl if the base url of the state is invalid
+    pub fn from_state(
+        state: ClientState,
+        client_secret: Option<String>,
+    ) -> Result<DRACOONClient, DRACOONClientError> {
+        let client_secret = client_secret
+            .or(state.client_secret)
+            .ok_or(DRACOONClientError::MissingArguments)?;
+
+        let base_url = Url::parse(&state.base_url)?;
+
+        let mut builder = DRACOONClient::builder(base_url, state.client_id, client_secret)
+            .with_max_retries(state.max_retries)
+            .with_max_concurrent_requests(state.max_concurrent_requests)
+            .with_default_page_size(state.default_page_size)
+            .with_max_response_body_size(state.max_response_body_size)
+            .with_method_override(state.method_override)
+            .with_pool_idle_timeout(state.pool_idle_timeout_ms.map(Duration::from_millis));
+
+        if let Some(scopes) = state.scopes {
+            builder = builder.with_scopes(scopes);
+        }
+
+        if let Some(timeout) = state.timeout_ms {
+            builder = builder.with_timeout(Duration::from_millis(timeout));
+        }
+
+        let mut client = builder.build()?;
+
+        if let Some(connection) = state.connection {
+            client.import_connection(connection);
+        }
+
+        Ok(client)
+    }
+}
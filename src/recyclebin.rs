@@ -0,0 +1,167 @@
+/// required imports
+use crate::core::{DRACOONClient, DRACOONClientError, DryRunAction, Paged};
+use crate::nodes::NodeType;
+use crate::uploads::ResolutionStrategy;
+use crate::users::UserInfo;
+use chrono::offset::Utc;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+
+/// constants for API urls
+const DRACOON_NODES_URL: &str = "nodes";
+const DRACOON_DELETED_NODES_URL: &str = "deleted_nodes";
+const DRACOON_DELETED_NODE_VERSIONS_URL: &str = "deleted_nodes/versions";
+const DRACOON_RESTORE_DELETED_NODES_URL: &str = "nodes/deleted_nodes/actions/restore";
+
+/// recycle bin entry of a parent: one entry per deleted name and type - all stored
+/// versions of it are listed with get_deleted_node_versions
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletedNodeSummary {
+    pub parent_id: Option<u64>,
+    pub parent_path: Option<String>,
+    #[serde(rename = "type")]
+    pub node_type: NodeType,
+    pub name: String,
+    /// number of stored versions
+    pub cnt_versions: Option<u64>,
+    pub first_deleted_at: Option<DateTime<Utc>>,
+    pub last_deleted_at: Option<DateTime<Utc>>,
+}
+
+/// stored version of a node: previous versions of an overwritten file and deleted nodes
+/// are both kept as deleted nodes (own id, not the id of the current node) - the API has
+/// no download for deleted nodes, their content is only accessible after a restore
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletedNode {
+    /// id of the deleted node (use for restore, not a node id)
+    pub id: u64,
+    pub parent_id: Option<u64>,
+    pub parent_path: Option<String>,
+    #[serde(rename = "type")]
+    pub node_type: NodeType,
+    pub name: String,
+    pub size: Option<u64>,
+    pub is_encrypted: Option<bool>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub created_by: Option<UserInfo>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub updated_by: Option<UserInfo>,
+    /// time the version was replaced or deleted
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub deleted_by: Option<UserInfo>,
+}
+
+/// request to restore deleted nodes (POST to restore url)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RestoreDeletedNodesRequest {
+    deleted_node_ids: Vec<u64>,
+    resolution_strategy: ResolutionStrategy,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_id: Option<u64>,
+}
+
+/// recycle bin (deleted nodes and file versions) implementation for DRACOON client
+/// (requires the permission to restore deleted nodes in the room - returns Forbidden
+/// otherwise)
+impl DRACOONClient {
+    /// lists the recycle bin of a parent (room or folder, paged) - one summary per name
+    pub async fn get_deleted_nodes(
+        &self,
+        parent_id: u64,
+        offset: Option<u64>,
+        limit: Option<u64>,
+        filter: Option<&str>,
+    ) -> Result<Paged<DeletedNodeSummary>, DRACOONClientError> {
+        let api_url = self.api_url(&format!(
+            "{}/{}/{}",
+            DRACOON_NODES_URL, parent_id, DRACOON_DELETED_NODES_URL
+        ));
+        let query = self.list_query(offset, limit, filter);
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url).query(&query))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// lists the stored versions of a name and type in a parent (paged, e.g. the entries
+    /// of a recycle bin summary)
+    pub async fn get_deleted_node_versions(
+        &self,
+        parent_id: u64,
+        node_type: NodeType,
+        name: &str,
+        offset: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<Paged<DeletedNode>, DRACOONClientError> {
+        let api_url = self.api_url(&format!(
+            "{}/{}/{}",
+            DRACOON_NODES_URL, parent_id, DRACOON_DELETED_NODE_VERSIONS_URL
+        ));
+
+        let mut query = self.list_query(offset, limit, None);
+        query.push(("type", String::from(node_type)));
+        query.push(("name", name.to_string()));
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url).query(&query))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// lists the previous versions of a node (paged) - the current version is the node
+    /// itself and not part of the list
+    pub async fn get_node_versions(
+        &self,
+        node_id: u64,
+        offset: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<Paged<DeletedNode>, DRACOONClientError> {
+        let node = self.get_node(node_id).await?;
+
+        self.get_deleted_node_versions(
+            node.parent_id.unwrap_or(0),
+            node.node_type,
+            &node.name,
+            offset,
+            limit,
+        )
+        .await
+    }
+
+    /// restores deleted nodes (e.g. a previous file version) into their original parent
+    /// or the given parent
+    /// - Overwrite replaces the current node and is skipped in dry run mode
+    pub async fn restore_deleted_nodes(
+        &self,
+        deleted_node_ids: &[u64],
+        parent_id: Option<u64>,
+        resolution_strategy: ResolutionStrategy,
+    ) -> Result<(), DRACOONClientError> {
+        if resolution_strategy == ResolutionStrategy::Overwrite {
+            self.check_dry_run(|| DryRunAction::RestoreNodes {
+                deleted_node_ids: deleted_node_ids.to_vec(),
+                parent_id,
+            })?;
+        }
+
+        let api_url = self.api_url(DRACOON_RESTORE_DELETED_NODES_URL);
+
+        let req = RestoreDeletedNodesRequest {
+            deleted_node_ids: deleted_node_ids.to_vec(),
+            resolution_strategy,
+            parent_id,
+        };
+
+        let res = self
+            .send_authenticated(self.http.post(api_url).json(&req))
+            .await?;
+
+        self.parse_empty_response(res).await
+    }
+}
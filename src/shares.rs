@@ -0,0 +1,576 @@
+/// required imports
+use crate::core::{DRACOONClient, DRACOONClientError, Expiration, MASKED_SECRET};
+use crate::crypto::{FileKey, UserKeyPairContainer};
+use crate::downloads::DownloadUrl;
+use crate::endpoints::Endpoints;
+use chrono::offset::Utc;
+use chrono::DateTime;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use tokio::io::AsyncWrite;
+use zeroize::Zeroizing;
+
+/// constants for API urls
+const DRACOON_DOWNLOAD_SHARES_URL: &str = "shares/downloads";
+const DRACOON_UPLOAD_SHARES_URL: &str = "shares/uploads";
+const DRACOON_SHARE_EMAIL_URL: &str = "email";
+const DRACOON_SHARE_SMS_URL: &str = "sms";
+
+/// checks the format of an email address (one @, non-empty local part, domain with a dot,
+/// no whitespace) - returns InvalidEmail with the address otherwise
+fn validate_email(email: &str) -> Result<(), DRACOONClientError> {
+    let valid = match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !email.chars().any(char::is_whitespace)
+        }
+        None => false,
+    };
+
+    match valid {
+        true => Ok(()),
+        false => Err(DRACOONClientError::InvalidEmail(email.to_string())),
+    }
+}
+
+/// download share (share link for a file or folder)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadShare {
+    pub id: u64,
+    pub name: String,
+    pub node_id: u64,
+    pub access_key: String,
+    pub is_protected: Option<bool>,
+    pub expire_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+    /// number of downloads so far
+    pub cnt_downloads: Option<u64>,
+    /// download limit of the share (None = unlimited)
+    pub max_downloads: Option<u64>,
+}
+
+impl DownloadShare {
+    /// remaining downloads until the limit is reached (None = unlimited)
+    pub fn remaining_downloads(&self) -> Option<u64> {
+        self.max_downloads
+            .map(|max| max.saturating_sub(self.cnt_downloads.unwrap_or(0)))
+    }
+
+    /// returns the public link of the share ({base_url}public/download-shares/{accessKey})
+    /// - never contains the share password, even for protected shares
+    pub fn public_link(&self, base_url: &Url) -> String {
+        Endpoints::new(base_url).public_download_link(&self.access_key)
+    }
+}
+
+/// upload share (file request for a room or folder)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadShare {
+    pub id: u64,
+    pub name: String,
+    pub target_id: u64,
+    pub access_key: String,
+    pub is_protected: Option<bool>,
+    pub expire_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub cnt_files: Option<u64>,
+}
+
+impl UploadShare {
+    /// returns the public link of the share ({base_url}public/upload-shares/{accessKey})
+    /// - never contains the share password, even for protected shares
+    pub fn public_link(&self, base_url: &Url) -> String {
+        Endpoints::new(base_url).public_upload_link(&self.access_key)
+    }
+}
+
+/// request to create a download share (POST to download shares url) - key pair and
+/// file key are set by create_download_share for files in encrypted rooms
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateDownloadShareRequest {
+    pub node_id: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration: Option<Expiration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_pair: Option<UserKeyPairContainer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_key: Option<FileKey>,
+    /// phone numbers to send the share password to via SMS (requires a share password)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_message_recipients: Option<Vec<String>>,
+    /// language of notifications to recipients (e.g. "de-DE")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receiver_language: Option<String>,
+}
+
+/// key pair and key pair password of the current user (shares in encrypted rooms) - the
+/// password is masked in Debug output
+#[derive(Clone, Copy)]
+pub struct ShareEncryption<'a> {
+    pub keypair: &'a UserKeyPairContainer,
+    pub password: &'a str,
+}
+
+impl fmt::Debug for ShareEncryption<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShareEncryption")
+            .field("keypair", &self.keypair)
+            .field("password", &MASKED_SECRET)
+            .finish()
+    }
+}
+
+/// request to create an upload share (POST to upload shares url)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateUploadShareRequest {
+    pub target_id: u64,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration: Option<Expiration>,
+    /// phone numbers to send the share password to via SMS (requires a share password)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_message_recipients: Option<Vec<String>>,
+    /// language of notifications to recipients (e.g. "de-DE")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receiver_language: Option<String>,
+}
+
+/// request to send a share link via email (POST to share email url)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareEmailRequest {
+    pub recipients: Vec<String>,
+    pub body: String,
+}
+
+/// request to update a download share (PUT to download share url) - only set fields
+/// are sent
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateDownloadShareRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reset_password: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration: Option<Expiration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_downloads: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reset_max_downloads: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+impl UpdateDownloadShareRequest {
+    /// request to set (Some) or remove (None) the share password
+    /// - shares of files in encrypted rooms keep the password of their share key pair
+    pub fn password(password: Option<&str>) -> Self {
+        match password {
+            Some(password) => UpdateDownloadShareRequest {
+                password: Some(password.to_string()),
+                ..Default::default()
+            },
+            None => UpdateDownloadShareRequest {
+                reset_password: Some(true),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// request to set (Some) or remove (None) the expiration date of the share
+    pub fn expiration(expire_at: Option<DateTime<Utc>>) -> Self {
+        UpdateDownloadShareRequest {
+            expiration: Some(Expiration::new(expire_at)),
+            ..Default::default()
+        }
+    }
+}
+
+/// request to update an upload share (PUT to upload share url) - only set fields are
+/// sent
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateUploadShareRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reset_password: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration: Option<Expiration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_slots: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reset_max_slots: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+impl UpdateUploadShareRequest {
+    /// request to set (Some) or remove (None) the share password
+    pub fn password(password: Option<&str>) -> Self {
+        match password {
+            Some(password) => UpdateUploadShareRequest {
+                password: Some(password.to_string()),
+                ..Default::default()
+            },
+            None => UpdateUploadShareRequest {
+                reset_password: Some(true),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// request to set (Some) or remove (None) the expiration date of the share
+    pub fn expiration(expire_at: Option<DateTime<Utc>>) -> Self {
+        UpdateUploadShareRequest {
+            expiration: Some(Expiration::new(expire_at)),
+            ..Default::default()
+        }
+    }
+}
+
+/// public download share metadata (no authentication required)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicDownloadShare {
+    pub is_protected: bool,
+    pub file_name: String,
+    pub size: Option<u64>,
+    pub expire_at: Option<DateTime<Utc>>,
+    pub limit_reached: Option<bool>,
+    pub creator_name: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub media_type: Option<String>,
+    pub is_encrypted: Option<bool>,
+}
+
+/// public upload share metadata (no authentication required)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicUploadShare {
+    pub is_protected: bool,
+    pub name: Option<String>,
+    pub expire_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub is_encrypted: Option<bool>,
+}
+
+/// request for the download url of a public download share (POST to public share url) -
+/// the password is the share password or the code received via SMS
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PublicDownloadUrlRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    password: Option<String>,
+}
+
+/// SMS challenge of a protected public download share (see request_share_sms) - complete
+/// the download with the received code (download_public_share_with_code)
+#[derive(Debug, Clone)]
+pub struct ShareSmsChallenge {
+    pub access_key: String,
+}
+
+/// supported share types
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShareType {
+    Download,
+    Upload,
+}
+
+/// summary of a share resolved by its access key
+#[derive(Debug)]
+pub struct ShareInfo {
+    pub share_type: ShareType,
+    pub name: Option<String>,
+    pub size: Option<u64>,
+    pub expire_at: Option<DateTime<Utc>>,
+    pub is_protected: bool,
+}
+
+impl From<PublicDownloadShare> for ShareInfo {
+    fn from(share: PublicDownloadShare) -> Self {
+        ShareInfo {
+            share_type: ShareType::Download,
+            name: Some(share.file_name),
+            size: share.size,
+            expire_at: share.expire_at,
+            is_protected: share.is_protected,
+        }
+    }
+}
+
+impl From<PublicUploadShare> for ShareInfo {
+    fn from(share: PublicUploadShare) -> Self {
+        ShareInfo {
+            share_type: ShareType::Upload,
+            name: share.name,
+            size: None,
+            expire_at: share.expire_at,
+            is_protected: share.is_protected,
+        }
+    }
+}
+
+/// share implementation for DRACOON client
+impl DRACOONClient {
+    /// creates a download share for a file or folder
+    /// - for files in encrypted rooms, the user key pair (see get_user_keypair) and its
+    ///   password as well as a share password are required (EncryptionRequired otherwise):
+    ///   the file key is decrypted and re-encrypted for a new share key pair protected by
+    ///   the share password - the share password itself is never sent (it would unlock
+    ///   the share key pair on the server)
+    pub async fn create_download_share(
+        &self,
+        req: &CreateDownloadShareRequest,
+        encryption: Option<ShareEncryption<'_>>,
+    ) -> Result<DownloadShare, DRACOONClientError> {
+        let node = self.get_node(req.node_id).await?;
+
+        let req = match node.is_encrypted {
+            Some(true) => {
+                let (encryption, share_password) = match (encryption, &req.password) {
+                    (Some(encryption), Some(share_password)) => (encryption, share_password),
+                    _ => return Err(DRACOONClientError::EncryptionRequired),
+                };
+
+                if !node.is_file() {
+                    return Err(DRACOONClientError::CryptoError(
+                        "only files in encrypted rooms can be shared".to_string(),
+                    ));
+                }
+
+                let file_key = self.get_user_file_key(node.id).await?;
+                let keypair = encryption.keypair.clone();
+                let password = Zeroizing::new(encryption.password.to_string());
+                let share_password = Zeroizing::new(share_password.clone());
+
+                // key pair generation and key derivation are CPU intensive
+                let (share_keypair, share_file_key) = tokio::task::spawn_blocking(move || {
+                    let plain_keypair = keypair.decrypt(&password)?;
+                    let plain_file_key = plain_keypair.decrypt_file_key(&file_key)?;
+                    let share_keypair = UserKeyPairContainer::generate(&share_password)?;
+                    let share_file_key = share_keypair.encrypt_file_key(&plain_file_key)?;
+
+                    Ok::<_, DRACOONClientError>((share_keypair, share_file_key))
+                })
+                .await
+                .map_err(|err| DRACOONClientError::CryptoError(err.to_string()))??;
+
+                CreateDownloadShareRequest {
+                    password: None,
+                    key_pair: Some(share_keypair),
+                    file_key: Some(share_file_key),
+                    ..req.clone()
+                }
+            }
+            _ => req.clone(),
+        };
+
+        let api_url = self.api_url(DRACOON_DOWNLOAD_SHARES_URL);
+
+        let res = self
+            .send_authenticated(self.http.post(api_url).json(&req))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// creates an upload share for a room or folder
+    pub async fn create_upload_share(
+        &self,
+        req: &CreateUploadShareRequest,
+    ) -> Result<UploadShare, DRACOONClientError> {
+        let api_url = self.api_url(DRACOON_UPLOAD_SHARES_URL);
+
+        let res = self
+            .send_authenticated(self.http.post(api_url).json(req))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// fetches a download share by id (incl. download count and limit)
+    pub async fn get_download_share(
+        &self,
+        share_id: u64,
+    ) -> Result<DownloadShare, DRACOONClientError> {
+        let api_url = self.api_url(&format!("{}/{}", DRACOON_DOWNLOAD_SHARES_URL, share_id));
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// updates a download share by id (password, expiration, max downloads, notes) -
+    /// returns the updated share
+    pub async fn update_download_share(
+        &self,
+        share_id: u64,
+        req: &UpdateDownloadShareRequest,
+    ) -> Result<DownloadShare, DRACOONClientError> {
+        let api_url = self.api_url(&format!("{}/{}", DRACOON_DOWNLOAD_SHARES_URL, share_id));
+
+        let res = self
+            .send_authenticated_idempotent(self.put(api_url).json(req))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// updates an upload share by id (password, expiration, max slots, notes) - returns
+    /// the updated share
+    pub async fn update_upload_share(
+        &self,
+        share_id: u64,
+        req: &UpdateUploadShareRequest,
+    ) -> Result<UploadShare, DRACOONClientError> {
+        let api_url = self.api_url(&format!("{}/{}", DRACOON_UPLOAD_SHARES_URL, share_id));
+
+        let res = self
+            .send_authenticated_idempotent(self.put(api_url).json(req))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// sends the link of a download share via email to the given recipients - the email
+    /// addresses are checked client-side first (InvalidEmail for the first invalid one)
+    /// - the share password is never part of the email
+    pub async fn send_share_email(
+        &self,
+        share_id: u64,
+        recipients: &[String],
+        body: &str,
+    ) -> Result<(), DRACOONClientError> {
+        if recipients.is_empty() {
+            return Err(DRACOONClientError::MissingArguments);
+        }
+
+        for recipient in recipients {
+            validate_email(recipient)?;
+        }
+
+        let req = ShareEmailRequest {
+            recipients: recipients.to_vec(),
+            body: body.to_string(),
+        };
+
+        let api_url = self.api_url(&format!(
+            "{}/{}/{}",
+            DRACOON_DOWNLOAD_SHARES_URL, share_id, DRACOON_SHARE_EMAIL_URL
+        ));
+
+        let res = self
+            .send_authenticated(self.http.post(api_url).json(&req))
+            .await?;
+
+        self.parse_empty_response(res).await
+    }
+
+    /// resolves a share by its access key (works unauthenticated) - looks up download
+    /// shares first and falls back to upload shares
+    pub async fn resolve_share(&self, access_key: &str) -> Result<ShareInfo, DRACOONClientError> {
+        let api_url = self.endpoints().public_download_share(access_key);
+
+        let res = self.send_with_retry(self.http.get(api_url)).await?;
+
+        if res.status() != reqwest::StatusCode::NOT_FOUND {
+            let share: PublicDownloadShare = self.parse_response(res).await?;
+            return Ok(share.into());
+        }
+
+        let api_url = self.endpoints().public_upload_share(access_key);
+
+        let res = self.send_with_retry(self.http.get(api_url)).await?;
+        let share: PublicUploadShare = self.parse_response(res).await?;
+
+        Ok(share.into())
+    }
+
+    /// requests the download url of a public download share (works unauthenticated) - the
+    /// password is required for protected shares (share password or SMS code)
+    pub async fn get_public_download_url(
+        &self,
+        access_key: &str,
+        password: Option<&str>,
+    ) -> Result<DownloadUrl, DRACOONClientError> {
+        let api_url = self.endpoints().public_download_share(access_key);
+
+        let req = PublicDownloadUrlRequest {
+            password: password.map(String::from),
+        };
+
+        let res = self.http.post(api_url).json(&req).send().await?;
+
+        self.parse_response(res).await
+    }
+
+    /// streams the file of a public download share into the given writer (works
+    /// unauthenticated) - returns the number of bytes written
+    pub async fn download_public_share<W: AsyncWrite + Unpin>(
+        &self,
+        access_key: &str,
+        password: Option<&str>,
+        writer: &mut W,
+    ) -> Result<u64, DRACOONClientError> {
+        let download_url = self.get_public_download_url(access_key, password).await?;
+
+        self.download_url_to_writer(&download_url.download_url, writer, None)
+            .await
+    }
+
+    /// requests the SMS with the code of an SMS protected public download share (works
+    /// unauthenticated) - never retried to avoid sending multiple codes
+    pub async fn request_share_sms(
+        &self,
+        access_key: &str,
+    ) -> Result<ShareSmsChallenge, DRACOONClientError> {
+        let api_url = format!(
+            "{}/{}",
+            self.endpoints().public_download_share(access_key),
+            DRACOON_SHARE_SMS_URL
+        );
+
+        let res = self.http.post(api_url).send().await?;
+        self.parse_empty_response(res).await?;
+
+        Ok(ShareSmsChallenge {
+            access_key: access_key.to_string(),
+        })
+    }
+
+    /// completes the download of an SMS protected public download share with the code
+    /// received for the challenge - returns the number of bytes written
+    pub async fn download_public_share_with_code<W: AsyncWrite + Unpin>(
+        &self,
+        challenge: &ShareSmsChallenge,
+        code: &str,
+        writer: &mut W,
+    ) -> Result<u64, DRACOONClientError> {
+        self.download_public_share(&challenge.access_key, Some(code), writer)
+            .await
+    }
+}
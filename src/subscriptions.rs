@@ -0,0 +1,68 @@
+/// required imports
+use crate::core::{DRACOONClient, DRACOONClientError, Paged};
+use serde::{Deserialize, Serialize};
+
+/// constants for API urls
+const DRACOON_NODES_URL: &str = "nodes";
+const DRACOON_SUBSCRIBE_URL: &str = "subscribe";
+const DRACOON_NODE_SUBSCRIPTIONS_URL: &str = "user/subscriptions/nodes";
+
+/// node subscription (notifications on node changes)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Subscription {
+    pub node_id: u64,
+    pub auth_parent_id: Option<u64>,
+}
+
+/// subscription implementation for DRACOON client
+impl DRACOONClient {
+    /// lists the node subscriptions of the current user (paged)
+    pub async fn get_node_subscriptions(
+        &self,
+        offset: Option<u64>,
+        limit: Option<u64>,
+        filter: Option<&str>,
+    ) -> Result<Paged<Subscription>, DRACOONClientError> {
+        let api_url = self.api_url(DRACOON_NODE_SUBSCRIPTIONS_URL);
+        let query = self.list_query(offset, limit, filter);
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url).query(&query))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// subscribes to a node - an existing subscription (409) counts as success
+    pub async fn subscribe_node(&self, node_id: u64) -> Result<(), DRACOONClientError> {
+        let api_url = self.api_url(&format!(
+            "{}/{}/{}",
+            DRACOON_NODES_URL, node_id, DRACOON_SUBSCRIBE_URL
+        ));
+
+        let res = self.send_authenticated(self.http.post(api_url)).await?;
+
+        match self.parse_empty_response(res).await {
+            Err(DRACOONClientError::DRACOONErrror(err)) if err.code == Some(409) => Ok(()),
+            res => res,
+        }
+    }
+
+    /// unsubscribes from a node - a missing subscription (404) counts as success
+    pub async fn unsubscribe_node(&self, node_id: u64) -> Result<(), DRACOONClientError> {
+        let api_url = self.api_url(&format!(
+            "{}/{}/{}",
+            DRACOON_NODES_URL, node_id, DRACOON_SUBSCRIBE_URL
+        ));
+
+        let res = self
+            .send_authenticated_idempotent(self.delete(api_url))
+            .await?;
+
+        match self.parse_empty_response(res).await {
+            Err(DRACOONClientError::DRACOONErrror(err)) if err.code == Some(404) => Ok(()),
+            res => res,
+        }
+    }
+}
@@ -0,0 +1,638 @@
+/// required imports
+use crate::core::{
+    with_transfer_timeout, DRACOONClient, DRACOONClientError, DryRunAction, Expiration, Paged,
+};
+use crate::crypto::{FileEncryptor, FileKey, PlainFileKey};
+use crate::nodes::{validate_node_name, Node};
+use chrono::offset::Utc;
+use chrono::DateTime;
+use reqwest::header::{CONTENT_RANGE, CONTENT_TYPE};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+/// constants for API urls
+const DRACOON_UPLOADS_URL: &str = "nodes/files/uploads";
+
+/// default chunk size for chunked uploads (32 MiB)
+pub const DEFAULT_UPLOAD_CHUNK_SIZE: usize = 32 * 1024 * 1024;
+
+/// default number of chunks queued between the pipeline stages (read, encrypt, upload)
+pub const DEFAULT_UPLOAD_PIPELINE_DEPTH: usize = 2;
+
+/// media type of files with unknown extension
+pub const DEFAULT_MEDIA_TYPE: &str = "application/octet-stream";
+
+/// media types by file extension (lowercase) - common documents, images, audio / video
+const MEDIA_TYPES: [(&str, &str); 40] = [
+    ("7z", "application/x-7z-compressed"),
+    ("avi", "video/x-msvideo"),
+    ("bmp", "image/bmp"),
+    ("csv", "text/csv"),
+    ("doc", "application/msword"),
+    (
+        "docx",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    ),
+    ("eml", "message/rfc822"),
+    ("gif", "image/gif"),
+    ("gz", "application/gzip"),
+    ("heic", "image/heic"),
+    ("htm", "text/html"),
+    ("html", "text/html"),
+    ("jpeg", "image/jpeg"),
+    ("jpg", "image/jpeg"),
+    ("js", "text/javascript"),
+    ("json", "application/json"),
+    ("md", "text/markdown"),
+    ("mov", "video/quicktime"),
+    ("mp3", "audio/mpeg"),
+    ("mp4", "video/mp4"),
+    ("odp", "application/vnd.oasis.opendocument.presentation"),
+    ("ods", "application/vnd.oasis.opendocument.spreadsheet"),
+    ("odt", "application/vnd.oasis.opendocument.text"),
+    ("pdf", "application/pdf"),
+    ("png", "image/png"),
+    ("ppt", "application/vnd.ms-powerpoint"),
+    (
+        "pptx",
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+    ),
+    ("rtf", "application/rtf"),
+    ("svg", "image/svg+xml"),
+    ("tar", "application/x-tar"),
+    ("tif", "image/tiff"),
+    ("tiff", "image/tiff"),
+    ("txt", "text/plain"),
+    ("wav", "audio/wav"),
+    ("webm", "video/webm"),
+    ("webp", "image/webp"),
+    ("xls", "application/vnd.ms-excel"),
+    (
+        "xlsx",
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+    ),
+    ("xml", "application/xml"),
+    ("zip", "application/zip"),
+];
+
+/// detects the media type of a file from the extension of its name (case-insensitive) -
+/// returns DEFAULT_MEDIA_TYPE for unknown or missing extensions
+pub fn detect_media_type(name: &str) -> &'static str {
+    let extension = match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => extension.to_ascii_lowercase(),
+        _ => return DEFAULT_MEDIA_TYPE,
+    };
+
+    MEDIA_TYPES
+        .iter()
+        .find(|(known, _)| *known == extension)
+        .map(|(_, media_type)| *media_type)
+        .unwrap_or(DEFAULT_MEDIA_TYPE)
+}
+
+/// conflict resolution for uploads (file with same name in parent)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResolutionStrategy {
+    AutoRename,
+    Overwrite,
+    Fail,
+}
+
+/// optional upload settings
+#[derive(Debug, Clone)]
+pub struct UploadOptions {
+    pub resolution_strategy: ResolutionStrategy,
+    pub chunk_size: usize,
+    /// number of chunks queued between the pipeline stages while the previous chunk is
+    /// uploaded (min. 1, default: DEFAULT_UPLOAD_PIPELINE_DEPTH) - memory use is up to
+    /// (depth + 2) * chunk_size, twice the queue for encrypted uploads
+    pub pipeline_depth: usize,
+    pub cancellation_token: Option<CancellationToken>,
+    /// checks the size against the remaining customer storage before creating the upload
+    /// channel (only if the size is known, default: true)
+    pub check_size: bool,
+    /// the uploaded file is deleted automatically after this date (default: None)
+    pub expire_at: Option<DateTime<Utc>>,
+    /// timeout of the chunk transfer only (channel creation and finalizing use the client
+    /// timeout) - overrides the client timeout, e.g. for a single huge file, and returns a
+    /// Timeout error once exceeded (default: None = client timeout)
+    pub timeout: Option<Duration>,
+    /// media type of the file (overrides the detection, default: None)
+    pub media_type: Option<String>,
+    /// detects the media type from the file name if none is given (see detect_media_type,
+    /// default: true) - used by DRACOON e.g. for previews
+    pub detect_media_type: bool,
+}
+
+impl Default for UploadOptions {
+    fn default() -> Self {
+        UploadOptions {
+            resolution_strategy: ResolutionStrategy::AutoRename,
+            chunk_size: DEFAULT_UPLOAD_CHUNK_SIZE,
+            pipeline_depth: DEFAULT_UPLOAD_PIPELINE_DEPTH,
+            cancellation_token: None,
+            check_size: true,
+            expire_at: None,
+            timeout: None,
+            media_type: None,
+            detect_media_type: true,
+        }
+    }
+}
+
+/// request to create an upload channel (POST to uploads url)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateUploadChannelRequest {
+    pub parent_id: u64,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration: Option<Expiration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+}
+
+/// created upload channel
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadChannel {
+    pub upload_id: String,
+    pub upload_url: String,
+    pub token: Option<String>,
+}
+
+/// pending upload channel of the current user (created, not finalized or cancelled yet)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingUpload {
+    pub upload_id: String,
+    pub parent_id: Option<u64>,
+    pub parent_path: Option<String>,
+    pub name: String,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// result of cleanup_stale_uploads - channels already gone (404) count as cancelled
+#[derive(Debug)]
+pub struct UploadCleanup {
+    pub cancelled: u64,
+    /// upload id and error of every channel that could not be cancelled
+    pub failed: Vec<(String, DRACOONClientError)>,
+}
+
+/// upload channel handed over to another system (see get_upload_ticket)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadTicket {
+    pub upload_id: String,
+    /// presigned url for the chunk PUT requests (no DRACOON authentication required)
+    pub upload_url: String,
+}
+
+impl From<UploadChannel> for UploadTicket {
+    fn from(channel: UploadChannel) -> Self {
+        UploadTicket {
+            upload_id: channel.upload_id,
+            upload_url: channel.upload_url,
+        }
+    }
+}
+
+/// request to finalize an upload (PUT to upload channel) - files in encrypted rooms
+/// require the file key encrypted for the current user
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompleteUploadRequest {
+    pub resolution_strategy: ResolutionStrategy,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_key: Option<FileKey>,
+}
+
+/// encryption stage of the upload pipeline (blocking pool): encrypts the chunks in order
+/// and returns the authentication tag - None if the upload stage stopped early
+fn encrypt_chunks(
+    mut encryptor: FileEncryptor,
+    mut chunks: mpsc::Receiver<(Vec<u8>, u64)>,
+    encrypted: mpsc::Sender<(Vec<u8>, u64)>,
+) -> Option<String> {
+    while let Some((chunk, offset)) = chunks.blocking_recv() {
+        let chunk = encryptor.encrypt(&chunk);
+
+        encrypted.blocking_send((chunk, offset)).ok()?;
+    }
+
+    Some(encryptor.finalize())
+}
+
+/// upload implementation for DRACOON client
+impl DRACOONClient {
+    /// creates an upload channel for a new file in the given parent (room or folder)
+    pub async fn create_upload_channel(
+        &self,
+        req: &CreateUploadChannelRequest,
+    ) -> Result<UploadChannel, DRACOONClientError> {
+        let api_url = self.api_url(DRACOON_UPLOADS_URL);
+
+        let res = self
+            .send_authenticated(self.http.post(api_url).json(req))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// creates an upload channel and returns its id and presigned upload url so that
+    /// another system can upload the file content (PUT with Content-Range)
+    /// - the upload url expires after a server-defined time (DRACOON removes unused
+    ///   upload channels), so it should be used promptly
+    /// - the caller is responsible for finalizing (complete_upload) or cancelling
+    ///   (cancel_upload) the channel
+    pub async fn get_upload_ticket(
+        &self,
+        parent_id: u64,
+        name: &str,
+        size: Option<u64>,
+    ) -> Result<UploadTicket, DRACOONClientError> {
+        validate_node_name(name)?;
+
+        let req = CreateUploadChannelRequest {
+            parent_id,
+            name: name.to_string(),
+            size,
+            expiration: None,
+            media_type: None,
+        };
+
+        let channel = self.create_upload_channel(&req).await?;
+
+        Ok(channel.into())
+    }
+
+    /// uploads a single chunk to the upload channel (offset = position of the chunk in the file)
+    async fn upload_chunk(
+        &self,
+        channel: &UploadChannel,
+        chunk: Vec<u8>,
+        offset: u64,
+        size: Option<u64>,
+        timeout: Option<Duration>,
+    ) -> Result<(), DRACOONClientError> {
+        let total = match size {
+            Some(size) => size.to_string(),
+            None => "*".to_string(),
+        };
+
+        let len = chunk.len() as u64;
+
+        let content_range = match len {
+            0 => format!("bytes */{}", total),
+            _ => format!("bytes {}-{}/{}", offset, offset + len - 1, total),
+        };
+
+        let mut req = self
+            .http
+            .put(&channel.upload_url)
+            .header(CONTENT_RANGE, content_range)
+            .header(CONTENT_TYPE, "application/octet-stream");
+
+        if let Some(timeout) = timeout {
+            req = req.timeout(timeout);
+        }
+
+        // chunks are idempotent (Content-Range) and safe to retry
+        let res = self.send_limited(req.body(chunk)).await?;
+
+        self.parse_empty_response(res).await?;
+        self.count_uploaded(len);
+
+        Ok(())
+    }
+
+    /// finalizes an upload channel - returns the created file
+    pub async fn complete_upload(
+        &self,
+        upload_id: &str,
+        req: &CompleteUploadRequest,
+    ) -> Result<Node, DRACOONClientError> {
+        let api_url = self.api_url(&format!("{}/{}", DRACOON_UPLOADS_URL, upload_id));
+
+        // never retried: finalizing twice could create duplicates
+        let res = self.send_authenticated(self.put(api_url).json(req)).await?;
+
+        self.parse_response(res).await
+    }
+
+    /// removes an upload channel (and all uploaded chunks) on the server - called
+    /// automatically when an upload fails, exposed for cleanup of abandoned channels
+    pub async fn cancel_upload(&self, upload_id: &str) -> Result<(), DRACOONClientError> {
+        let api_url = self.api_url(&format!("{}/{}", DRACOON_UPLOADS_URL, upload_id));
+
+        let res = self
+            .send_authenticated_idempotent(self.delete(api_url))
+            .await?;
+
+        self.parse_empty_response(res).await
+    }
+
+    /// lists the pending upload channels of the current user (paged) - e.g. channels left
+    /// behind by crashed transfers
+    pub async fn get_uploads(
+        &self,
+        offset: Option<u64>,
+        limit: Option<u64>,
+        filter: Option<&str>,
+    ) -> Result<Paged<PendingUpload>, DRACOONClientError> {
+        let api_url = self.api_url(DRACOON_UPLOADS_URL);
+        let query = self.list_query(offset, limit, filter);
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url).query(&query))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// cancels all pending upload channels created more than older_than ago (reclaims
+    /// storage and channel limits of dead transfers) - returns the number of cancelled
+    /// channels and the channels that failed
+    /// - channels without creation date are kept, all pages are listed before cancelling
+    ///   (cancelling shifts the offsets of the following pages)
+    /// - a failed cancellation does not stop the cleanup, only listing errors are returned
+    pub async fn cleanup_stale_uploads(
+        &self,
+        older_than: Duration,
+    ) -> Result<UploadCleanup, DRACOONClientError> {
+        // a cutoff beyond the representable dates matches no channel
+        let cutoff = match chrono::Duration::from_std(older_than)
+            .ok()
+            .and_then(|older_than| self.now().checked_sub_signed(older_than))
+        {
+            Some(cutoff) => cutoff,
+            None => {
+                return Ok(UploadCleanup {
+                    cancelled: 0,
+                    failed: Vec::new(),
+                })
+            }
+        };
+
+        let mut uploads = Vec::new();
+        loop {
+            let page = self
+                .get_uploads(Some(uploads.len() as u64), None, None)
+                .await?;
+            let total = page.range.total;
+            let empty = page.items.is_empty();
+            uploads.extend(page.items);
+
+            if empty || uploads.len() as u64 >= total {
+                break;
+            }
+        }
+
+        let mut cleanup = UploadCleanup {
+            cancelled: 0,
+            failed: Vec::new(),
+        };
+
+        for upload in uploads.into_iter().filter(|upload| {
+            upload
+                .created_at
+                .is_some_and(|created_at| created_at < cutoff)
+        }) {
+            match self.cancel_upload(&upload.upload_id).await {
+                Ok(()) => cleanup.cancelled += 1,
+                Err(DRACOONClientError::DRACOONErrror(err)) if err.code == Some(404) => {
+                    cleanup.cancelled += 1
+                }
+                Err(err) => cleanup.failed.push((upload.upload_id, err)),
+            }
+        }
+
+        Ok(cleanup)
+    }
+
+    /// uploads a file from any AsyncRead via upload channel (chunked) and finalizes it
+    /// - size is optional and used for the Content-Range of the chunks - if known, it is
+    ///   checked against the remaining storage first (FileTooLarge, see check_size option)
+    /// - if the cancellation token in the options is cancelled, the transfer is aborted
+    ///   and a Cancelled error is returned
+    /// - on any error (including cancellation) the upload channel is removed on the server
+    /// - uploads into encrypted rooms are encrypted client-side with a new file key, which
+    ///   is stored encrypted for the key pair of the current user (only the public key is
+    ///   used, no key pair password required)
+    pub async fn upload<R: AsyncRead + Unpin>(
+        &self,
+        parent_id: u64,
+        name: &str,
+        reader: &mut R,
+        size: Option<u64>,
+        options: UploadOptions,
+    ) -> Result<Node, DRACOONClientError> {
+        validate_node_name(name)?;
+
+        if options.resolution_strategy == ResolutionStrategy::Overwrite {
+            self.check_dry_run(|| DryRunAction::OverwriteUpload {
+                parent_id,
+                name: name.to_string(),
+            })?;
+        }
+
+        // uncached: a cached parent might not be encrypted yet (never upload plain text)
+        let encryption = match self.fetch_node(parent_id).await?.is_encrypted {
+            Some(true) => Some((self.get_user_keypair().await?, PlainFileKey::generate())),
+            _ => None,
+        };
+        let encryptor = encryption
+            .as_ref()
+            .map(|(_, file_key)| FileEncryptor::new(file_key))
+            .transpose()?;
+
+        if let (true, Some(size)) = (options.check_size, size) {
+            self.check_upload_size(size).await?;
+        }
+
+        let cancellation_token = options.cancellation_token.clone().unwrap_or_default();
+
+        let channel_req = CreateUploadChannelRequest {
+            parent_id,
+            name: name.to_string(),
+            size,
+            expiration: options.expire_at.map(|date| Expiration::new(Some(date))),
+            media_type: match (&options.media_type, options.detect_media_type) {
+                (Some(media_type), _) => Some(media_type.clone()),
+                (None, true) => Some(detect_media_type(name).to_string()),
+                (None, false) => None,
+            },
+        };
+
+        let channel = tokio::select! {
+            _ = cancellation_token.cancelled() => return Err(DRACOONClientError::Cancelled),
+            channel = self.create_upload_channel(&channel_req) => channel?,
+        };
+
+        let chunks = self.upload_chunks(&channel, reader, size, &options, encryptor);
+
+        let transfer = tokio::select! {
+            _ = cancellation_token.cancelled() => Err(DRACOONClientError::Cancelled),
+            res = with_transfer_timeout(options.timeout, chunks) => res,
+        };
+
+        let result = match transfer {
+            Ok(tag) => {
+                let file_key = match (encryption, tag) {
+                    (Some((keypair, mut file_key)), Some(tag)) => {
+                        file_key.set_tag(tag);
+                        keypair.encrypt_file_key(&file_key).map(Some)
+                    }
+                    _ => Ok(None),
+                };
+
+                match file_key {
+                    Ok(file_key) => {
+                        self.complete_upload(
+                            &channel.upload_id,
+                            &CompleteUploadRequest {
+                                resolution_strategy: options.resolution_strategy,
+                                file_key,
+                            },
+                        )
+                        .await
+                    }
+                    Err(err) => Err(err),
+                }
+            }
+            Err(err) => Err(err),
+        };
+
+        if result.is_err() {
+            // best effort: avoid orphaned upload channels on the server
+            let _ = self.cancel_upload(&channel.upload_id).await;
+        }
+
+        result
+    }
+
+    /// reads the given reader in chunks and uploads them to the upload channel as a
+    /// pipeline: reading, encryption and the chunk PUTs run concurrently, connected by
+    /// bounded queues of pipeline_depth chunks - the chunks are uploaded in order
+    /// - with an encryptor, the chunks are encrypted (AES-256-GCM incl. GHASH) on the
+    ///   blocking pool (spawn_blocking), so the CPU-bound crypto overlaps with the network
+    ///   I/O - returns the authentication tag of the encrypted file then
+    async fn upload_chunks<R: AsyncRead + Unpin>(
+        &self,
+        channel: &UploadChannel,
+        reader: &mut R,
+        size: Option<u64>,
+        options: &UploadOptions,
+        encryptor: Option<FileEncryptor>,
+    ) -> Result<Option<String>, DRACOONClientError> {
+        let chunk_size = options.chunk_size.max(1);
+        let depth = options.pipeline_depth.max(1);
+        let (sender, receiver) = mpsc::channel::<(Vec<u8>, u64)>(depth);
+
+        // AES-GCM keeps the length: offsets and size stay the same for encrypted chunks
+        let (mut receiver, encryption) = match encryptor {
+            None => (receiver, None),
+            Some(encryptor) => {
+                let (encrypted_sender, encrypted_receiver) = mpsc::channel(depth);
+                let encryption = tokio::task::spawn_blocking(move || {
+                    encrypt_chunks(encryptor, receiver, encrypted_sender)
+                });
+
+                (encrypted_receiver, Some(encryption))
+            }
+        };
+
+        let read_chunks = async move {
+            let mut offset: u64 = 0;
+
+            loop {
+                let mut chunk = Vec::with_capacity(chunk_size);
+                (&mut *reader)
+                    .take(chunk_size as u64)
+                    .read_to_end(&mut chunk)
+                    .await?;
+
+                let len = chunk.len() as u64;
+
+                // always send at least one chunk (empty files)
+                if len == 0 && offset > 0 {
+                    break;
+                }
+
+                // the upload side stopped (error) - its error is returned
+                if sender.send((chunk, offset)).await.is_err() {
+                    break;
+                }
+
+                offset += len;
+
+                if len < chunk_size as u64 {
+                    break;
+                }
+            }
+
+            Ok::<(), DRACOONClientError>(())
+        };
+
+        let upload_chunks = async {
+            while let Some((chunk, offset)) = receiver.recv().await {
+                self.upload_chunk(channel, chunk, offset, size, options.timeout)
+                    .await?;
+            }
+
+            Ok::<(), DRACOONClientError>(())
+        };
+
+        tokio::try_join!(read_chunks, upload_chunks)?;
+
+        match encryption {
+            Some(encryption) => match encryption.await {
+                Ok(Some(tag)) => Ok(Some(tag)),
+                _ => Err(DRACOONClientError::CryptoError(
+                    "chunk encryption failed".to_string(),
+                )),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// uploads an in-memory buffer as a file (upload channel, see upload - encrypted in
+    /// encrypted rooms)
+    pub async fn upload_bytes(
+        &self,
+        parent_id: u64,
+        name: &str,
+        data: &[u8],
+        options: UploadOptions,
+    ) -> Result<Node, DRACOONClientError> {
+        let mut reader = data;
+
+        self.upload(
+            parent_id,
+            name,
+            &mut reader,
+            Some(data.len() as u64),
+            options,
+        )
+        .await
+    }
+
+    /// uploads a string as a (UTF-8) text file
+    pub async fn upload_string(
+        &self,
+        parent_id: u64,
+        name: &str,
+        data: &str,
+        options: UploadOptions,
+    ) -> Result<Node, DRACOONClientError> {
+        self.upload_bytes(parent_id, name, data.as_bytes(), options)
+            .await
+    }
+}
@@ -0,0 +1,146 @@
+/// required imports
+use crate::core::{DRACOONClient, DRACOONClientError, Expiration};
+use chrono::offset::Utc;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+
+/// constants for API urls
+const DRACOON_USERS_URL: &str = "users";
+
+/// DRACOON user (user management, requires user manager role)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserItem {
+    pub id: u64,
+    pub user_name: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub email: Option<String>,
+    pub is_locked: bool,
+    pub expire_at: Option<DateTime<Utc>>,
+    pub last_login_at: Option<DateTime<Utc>>,
+}
+
+/// short user info (e.g. group members, node creator)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserInfo {
+    pub id: u64,
+    pub user_name: Option<String>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub email: Option<String>,
+}
+
+impl UserInfo {
+    /// display name of the user ("first last", falls back to the user name)
+    pub fn display_name(&self) -> Option<String> {
+        let name: Vec<&str> = [&self.first_name, &self.last_name]
+            .iter()
+            .filter_map(|part| part.as_deref())
+            .filter(|part| !part.is_empty())
+            .collect();
+
+        match name.is_empty() {
+            true => self.user_name.clone(),
+            false => Some(name.join(" ")),
+        }
+    }
+}
+
+/// request to update a user (PUT to user url) - only set fields are sent
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateUserRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_locked: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration: Option<Expiration>,
+}
+
+impl UpdateUserRequest {
+    /// request to lock (true) or unlock (false) a user
+    pub fn lock(locked: bool) -> Self {
+        UpdateUserRequest {
+            is_locked: Some(locked),
+            ..Default::default()
+        }
+    }
+
+    /// request to set (Some) or remove (None) the expiration date of a user
+    pub fn expiration(expire_at: Option<DateTime<Utc>>) -> Self {
+        UpdateUserRequest {
+            expiration: Some(Expiration::new(expire_at)),
+            ..Default::default()
+        }
+    }
+}
+
+/// user management implementation for DRACOON client
+/// (all methods require the user manager role - returns Forbidden otherwise)
+impl DRACOONClient {
+    /// fetches a user by id
+    pub async fn get_user(&self, user_id: u64) -> Result<UserItem, DRACOONClientError> {
+        let api_url = self.api_url(&format!("{}/{}", DRACOON_USERS_URL, user_id));
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// updates a user by id - returns the updated user
+    pub async fn update_user(
+        &self,
+        user_id: u64,
+        req: &UpdateUserRequest,
+    ) -> Result<UserItem, DRACOONClientError> {
+        let api_url = self.api_url(&format!("{}/{}", DRACOON_USERS_URL, user_id));
+
+        let res = self
+            .send_authenticated_idempotent(self.put(api_url).json(req))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// locks (true) or unlocks (false) a user account
+    pub async fn lock_user(
+        &self,
+        user_id: u64,
+        locked: bool,
+    ) -> Result<UserItem, DRACOONClientError> {
+        self.update_user(user_id, &UpdateUserRequest::lock(locked))
+            .await
+    }
+
+    /// sets the expiration date of a user account (None removes the expiration)
+    pub async fn set_user_expiration(
+        &self,
+        user_id: u64,
+        expire_at: Option<DateTime<Utc>>,
+    ) -> Result<UserItem, DRACOONClientError> {
+        self.update_user(user_id, &UpdateUserRequest::expiration(expire_at))
+            .await
+    }
+
+    /// deletes a user by id
+    pub async fn delete_user(&self, user_id: u64) -> Result<(), DRACOONClientError> {
+        let api_url = self.api_url(&format!("{}/{}", DRACOON_USERS_URL, user_id));
+
+        let res = self
+            .send_authenticated_idempotent(self.delete(api_url))
+            .await?;
+
+        self.parse_empty_response(res).await
+    }
+}
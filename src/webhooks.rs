@@ -0,0 +1,247 @@
+/// required imports
+use crate::config::ApiVersion;
+use crate::core::{masked, DRACOONClient, DRACOONClientError, Paged};
+use chrono::offset::Utc;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// constants for API urls
+const DRACOON_WEBHOOKS_URL: &str = "settings/webhooks";
+const DRACOON_ROOMS_URL: &str = "nodes/rooms";
+const DRACOON_ROOM_WEBHOOKS_URL: &str = "webhooks";
+
+/// minimum API version of the webhook endpoints (customer and room webhooks)
+const WEBHOOKS_MIN_VERSION: ApiVersion = ApiVersion::new(4, 19, 0);
+
+/// DRACOON webhook (customer scope) - the secret signs the webhook payloads and is
+/// masked in Debug output
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    pub id: u64,
+    pub name: String,
+    pub url: String,
+    pub secret: Option<String>,
+    pub is_enabled: bool,
+    pub event_type_names: Vec<String>,
+    pub expire_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    /// HTTP status of the last failed delivery (webhook gets disabled after failures)
+    pub fail_status: Option<u16>,
+}
+
+impl fmt::Debug for Webhook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Webhook")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .field("url", &self.url)
+            .field("secret", &masked(&self.secret))
+            .field("is_enabled", &self.is_enabled)
+            .field("event_type_names", &self.event_type_names)
+            .field("expire_at", &self.expire_at)
+            .field("created_at", &self.created_at)
+            .field("updated_at", &self.updated_at)
+            .field("fail_status", &self.fail_status)
+            .finish()
+    }
+}
+
+/// request to create a webhook (POST to webhooks url) - event type names as listed by
+/// DRACOON (e.g. "downloadshare.created", "file.created")
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookConfig {
+    pub name: String,
+    pub url: String,
+    pub event_type_names: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_enabled: Option<bool>,
+}
+
+impl fmt::Debug for WebhookConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebhookConfig")
+            .field("name", &self.name)
+            .field("url", &self.url)
+            .field("event_type_names", &self.event_type_names)
+            .field("secret", &masked(&self.secret))
+            .field("is_enabled", &self.is_enabled)
+            .finish()
+    }
+}
+
+/// request to update a webhook (PUT to webhook url) - only set fields are sent
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateWebhookRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_type_names: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_enabled: Option<bool>,
+}
+
+impl fmt::Debug for UpdateWebhookRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UpdateWebhookRequest")
+            .field("name", &self.name)
+            .field("url", &self.url)
+            .field("event_type_names", &self.event_type_names)
+            .field("secret", &masked(&self.secret))
+            .field("is_enabled", &self.is_enabled)
+            .finish()
+    }
+}
+
+/// webhook available for a room and whether it is assigned to it
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomWebhook {
+    pub is_assigned: bool,
+    pub webhook: Webhook,
+}
+
+/// assignment of a webhook to a room
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RoomWebhookAssignment {
+    webhook_id: u64,
+    is_assigned: bool,
+}
+
+/// request to update the webhook assignments of a room (PUT to room webhooks url)
+#[derive(Debug, Serialize, Deserialize)]
+struct UpdateRoomWebhooksRequest {
+    items: Vec<RoomWebhookAssignment>,
+}
+
+/// webhook implementation for DRACOON client
+/// (customer webhooks require the config manager role, room webhooks the room
+/// manager role - returns Forbidden otherwise, FeatureUnavailable on servers before 4.19)
+impl DRACOONClient {
+    /// lists the webhooks of the customer (paged)
+    pub async fn get_webhooks(
+        &self,
+        offset: Option<u64>,
+        limit: Option<u64>,
+        filter: Option<&str>,
+    ) -> Result<Paged<Webhook>, DRACOONClientError> {
+        self.requires_version(WEBHOOKS_MIN_VERSION).await?;
+
+        let api_url = self.api_url(DRACOON_WEBHOOKS_URL);
+        let query = self.list_query(offset, limit, filter);
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url).query(&query))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// creates a webhook
+    pub async fn create_webhook(&self, req: &WebhookConfig) -> Result<Webhook, DRACOONClientError> {
+        self.requires_version(WEBHOOKS_MIN_VERSION).await?;
+
+        let api_url = self.api_url(DRACOON_WEBHOOKS_URL);
+
+        let res = self
+            .send_authenticated(self.http.post(api_url).json(req))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// updates a webhook by id - returns the updated webhook
+    pub async fn update_webhook(
+        &self,
+        webhook_id: u64,
+        req: &UpdateWebhookRequest,
+    ) -> Result<Webhook, DRACOONClientError> {
+        self.requires_version(WEBHOOKS_MIN_VERSION).await?;
+
+        let api_url = self.api_url(&format!("{}/{}", DRACOON_WEBHOOKS_URL, webhook_id));
+
+        let res = self
+            .send_authenticated_idempotent(self.put(api_url).json(req))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// deletes a webhook by id
+    pub async fn delete_webhook(&self, webhook_id: u64) -> Result<(), DRACOONClientError> {
+        self.requires_version(WEBHOOKS_MIN_VERSION).await?;
+
+        let api_url = self.api_url(&format!("{}/{}", DRACOON_WEBHOOKS_URL, webhook_id));
+
+        let res = self
+            .send_authenticated_idempotent(self.delete(api_url))
+            .await?;
+
+        self.parse_empty_response(res).await
+    }
+
+    /// lists the webhooks available for a room (paged) with their assignment state
+    pub async fn get_room_webhooks(
+        &self,
+        room_id: u64,
+        offset: Option<u64>,
+        limit: Option<u64>,
+        filter: Option<&str>,
+    ) -> Result<Paged<RoomWebhook>, DRACOONClientError> {
+        self.requires_version(WEBHOOKS_MIN_VERSION).await?;
+
+        let api_url = self.api_url(&format!(
+            "{}/{}/{}",
+            DRACOON_ROOMS_URL, room_id, DRACOON_ROOM_WEBHOOKS_URL
+        ));
+        let query = self.list_query(offset, limit, filter);
+
+        let res = self
+            .send_authenticated_idempotent(self.http.get(api_url).query(&query))
+            .await?;
+
+        self.parse_response(res).await
+    }
+
+    /// assigns (true) or unassigns (false) webhooks to / from a room - returns the
+    /// updated assignments
+    pub async fn assign_room_webhooks(
+        &self,
+        room_id: u64,
+        assignments: &[(u64, bool)],
+    ) -> Result<Paged<RoomWebhook>, DRACOONClientError> {
+        self.requires_version(WEBHOOKS_MIN_VERSION).await?;
+
+        let api_url = self.api_url(&format!(
+            "{}/{}/{}",
+            DRACOON_ROOMS_URL, room_id, DRACOON_ROOM_WEBHOOKS_URL
+        ));
+
+        let req = UpdateRoomWebhooksRequest {
+            items: assignments
+                .iter()
+                .map(|(webhook_id, is_assigned)| RoomWebhookAssignment {
+                    webhook_id: *webhook_id,
+                    is_assigned: *is_assigned,
+                })
+                .collect(),
+        };
+
+        let res = self
+            .send_authenticated_idempotent(self.put(api_url).json(&req))
+            .await?;
+
+        self.parse_response(res).await
+    }
+}
@@ -0,0 +1,114 @@
+use dracoon_oxide::core::DRACOONClientError;
+use serde_json::json;
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+async fn mount_password_policies(server: &MockServer) {
+    common::mount_software_version(server, "4.42.2").await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/config/policies/passwords"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "loginPasswordPolicies": {
+                "minLength": 12,
+                "characterRules": {
+                    "mustContainCharacters": ["uppercase", "numeric"],
+                    "numberOfCharacteristicsToEnforce": 0
+                }
+            }
+        })))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn change_own_password_sends_old_and_new_password() {
+    let server = MockServer::start().await;
+    mount_password_policies(&server).await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/user/account/password"))
+        .and(body_json(json!({
+            "oldPassword": "Old-Password-1",
+            "newPassword": "New-Password-2"
+        })))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    client
+        .change_own_password("Old-Password-1", "New-Password-2")
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn change_own_password_validates_policy_first() {
+    let server = MockServer::start().await;
+    mount_password_policies(&server).await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/user/account/password"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    let res = client.change_own_password("Old-Password-1", "short").await;
+
+    assert!(matches!(res, Err(DRACOONClientError::InvalidPassword(_))));
+}
+
+#[tokio::test]
+async fn whoami_keeps_account_if_customer_info_fails() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/user/account"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 1,
+            "userName": "jdoe",
+            "firstName": "Jane",
+            "lastName": "Doe",
+            "userRoles": { "items": [{ "id": 1, "name": "CONFIG_MANAGER" }] }
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/user/account/customer"))
+        .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+            "code": 403,
+            "message": "Forbidden"
+        })))
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+    let whoami = client.whoami().await.unwrap();
+
+    assert_eq!(whoami.account.unwrap().user_name, "jdoe");
+    assert!(matches!(
+        whoami.customer,
+        Err(DRACOONClientError::Forbidden(_))
+    ));
+    assert_eq!(whoami.connection.scopes, vec!["all".to_string()]);
+}
+
+#[tokio::test]
+async fn whoami_requires_connection() {
+    let server = MockServer::start().await;
+    let client = common::builder(&server).build().unwrap();
+
+    assert!(matches!(
+        client.whoami().await,
+        Err(DRACOONClientError::BrokenConnection)
+    ));
+}
@@ -0,0 +1,71 @@
+use dracoon_oxide::core::{authorize_url, AuthorizeUrlOptions, Branding, DRACOONClient};
+use reqwest::Url;
+
+fn base_url() -> Url {
+    Url::parse("https://dracoon.example.com/").unwrap()
+}
+
+fn query(url: &Url) -> Vec<(String, String)> {
+    url.query_pairs()
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect()
+}
+
+#[test]
+fn default_authorize_url_matches_previous_format() {
+    let url = authorize_url(&base_url(), "client-id", &AuthorizeUrlOptions::default());
+
+    assert_eq!(url.path(), "/oauth/authorize");
+    assert_eq!(
+        query(&url),
+        vec![
+            ("branding".to_string(), "full".to_string()),
+            ("response_type".to_string(), "code".to_string()),
+            ("client_id".to_string(), "client-id".to_string()),
+            (
+                "redirect_uri".to_string(),
+                "https://dracoon.example.com/oauth/callback".to_string()
+            ),
+            ("scope".to_string(), "all".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn authorize_url_uses_given_options() {
+    let options = AuthorizeUrlOptions {
+        branding: Branding::None,
+        state: Some("a b&c".to_string()),
+        ..Default::default()
+    };
+
+    let url = authorize_url(&base_url(), "client-id", &options);
+    let query = query(&url);
+
+    assert_eq!(query[0], ("branding".to_string(), "none".to_string()));
+    assert_eq!(query[5], ("state".to_string(), "a b&c".to_string()));
+}
+
+#[test]
+fn authorize_url_keeps_base_url_path() {
+    let base_url = Url::parse("https://example.com/dracoon/").unwrap();
+
+    let url = authorize_url(&base_url, "client-id", &AuthorizeUrlOptions::default());
+
+    assert_eq!(url.path(), "/dracoon/oauth/authorize");
+}
+
+#[test]
+fn client_code_url_uses_defaults() {
+    let client = DRACOONClient::new(
+        "https://dracoon.example.com",
+        "client-id".to_string(),
+        "client-secret".to_string(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        client.get_code_url(),
+        authorize_url(&base_url(), "client-id", &AuthorizeUrlOptions::default())
+    );
+}
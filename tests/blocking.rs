@@ -0,0 +1,38 @@
+#![cfg(feature = "blocking")]
+
+use dracoon_oxide::blocking::DRACOONClient;
+use dracoon_oxide::core::DRACOONClientError;
+
+fn sample_client() -> DRACOONClient {
+    DRACOONClient::new(
+        "https://dracoon.example.com/",
+        "client-id".to_string(),
+        "client-secret".to_string(),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn calls_within_async_context_return_error_instead_of_panicking() {
+    let client = sample_client();
+
+    let res = client.test_connection();
+
+    assert!(matches!(
+        res,
+        Err(DRACOONClientError::BlockingInAsyncContext)
+    ));
+
+    // dropping the internal runtime within an async context must not panic either
+    drop(client);
+}
+
+#[test]
+fn calls_outside_async_context_use_internal_runtime() {
+    let client = sample_client();
+
+    // not connected: the call reaches the async client instead of failing early
+    let res = client.get_node(1);
+
+    assert!(matches!(res, Err(DRACOONClientError::BrokenConnection)));
+}
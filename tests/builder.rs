@@ -0,0 +1,69 @@
+use dracoon_oxide::core::{DRACOONClient, DRACOONClientBuilder, DRACOONClientError};
+use reqwest::Url;
+
+fn sample_builder() -> DRACOONClientBuilder {
+    DRACOONClient::builder(
+        Url::parse("https://dracoon.example.com/").unwrap(),
+        "client-id".to_string(),
+        "client-secret".to_string(),
+    )
+}
+
+#[test]
+fn invalid_root_certificate_is_rejected() {
+    let res = sample_builder().add_root_certificate(b"not a certificate");
+
+    assert!(matches!(
+        res,
+        Err(DRACOONClientError::InvalidCertificate(_))
+    ));
+}
+
+#[test]
+fn missing_root_certificate_file_returns_io_error() {
+    let res = sample_builder().add_root_certificate_from_file("/nonexistent/ca.pem");
+
+    assert!(matches!(res, Err(DRACOONClientError::IoError(_))));
+}
+
+#[test]
+fn malformed_base_url_returns_error() {
+    let res = DRACOONClient::new(
+        "not a url",
+        "client-id".to_string(),
+        "client-secret".to_string(),
+    );
+
+    assert!(matches!(res, Err(DRACOONClientError::InvalidUrl(_))));
+}
+
+#[test]
+fn base_url_which_cannot_be_a_base_returns_error() {
+    let res = DRACOONClient::builder(
+        Url::parse("mailto:admin@example.com").unwrap(),
+        "client-id".to_string(),
+        "client-secret".to_string(),
+    )
+    .build();
+
+    assert!(matches!(res, Err(DRACOONClientError::InvalidUrl(_))));
+}
+
+#[test]
+fn config_reports_effective_settings_without_secret() {
+    let client = sample_builder()
+        .with_timeout(std::time::Duration::from_secs(30))
+        .with_max_retries(5)
+        .build()
+        .unwrap();
+
+    let config = client.config();
+
+    assert_eq!(config.base_url.as_str(), "https://dracoon.example.com/");
+    assert_eq!(config.api_prefix, "api/v4");
+    assert_eq!(config.timeout, Some(std::time::Duration::from_secs(30)));
+    assert_eq!(config.max_retries, 5);
+    assert!(config.user_agent.is_some());
+    assert!(!config.shared_http);
+    assert!(!format!("{:?}", config).contains("client-secret"));
+}
@@ -0,0 +1,241 @@
+use chrono::Utc;
+use dracoon_oxide::core::{DRACOONClient, DRACOONClientError, MockClock, OAuth2ConnectionType};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use wiremock::matchers::{body_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+async fn connected_client(server: &MockServer, ttl: Duration) -> DRACOONClient {
+    let client = common::builder(server)
+        .with_metadata_cache(ttl)
+        .build()
+        .unwrap();
+
+    common::connect(server, client).await
+}
+
+fn sample_node() -> serde_json::Value {
+    json!({ "id": 1, "name": "Room", "type": "room" })
+}
+
+#[tokio::test]
+async fn fresh_cache_entry_is_returned_without_request() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(sample_node()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = connected_client(&server, Duration::from_secs(60)).await;
+
+    assert_eq!(client.get_node(1).await.unwrap().name, "Room");
+    assert_eq!(client.get_node(1).await.unwrap().name, "Room");
+
+    client.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn stale_cache_entry_is_revalidated_with_etag() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .and(header("if-none-match", "\"v1\""))
+        .respond_with(ResponseTemplate::new(304))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("ETag", "\"v1\"")
+                .set_body_json(sample_node()),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = connected_client(&server, Duration::from_secs(0)).await;
+
+    assert_eq!(client.get_node(1).await.unwrap().name, "Room");
+    assert_eq!(client.get_node(1).await.unwrap().name, "Room");
+
+    client.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn cache_entry_expires_with_client_clock() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(sample_node()))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let clock = Arc::new(MockClock::new(Utc::now()));
+    let client = common::builder(&server)
+        .with_metadata_cache(Duration::from_secs(60))
+        .with_clock(clock.clone())
+        .build()
+        .unwrap();
+    let client = common::connect(&server, client).await;
+
+    assert_eq!(client.get_node(1).await.unwrap().name, "Room");
+
+    clock.advance(chrono::Duration::seconds(59));
+    assert_eq!(client.get_node(1).await.unwrap().name, "Room");
+
+    // stale without waiting: fetched again
+    clock.advance(chrono::Duration::seconds(2));
+    assert_eq!(client.get_node(1).await.unwrap().name, "Room");
+
+    client.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn reconnect_clears_cache_of_previous_user() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(sample_node()))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let mut client = connected_client(&server, Duration::from_secs(60)).await;
+
+    assert_eq!(client.get_node(1).await.unwrap().name, "Room");
+
+    // another user on the same (shared) client: the cached node must not be reused
+    client
+        .connect(OAuth2ConnectionType::PasswordFlow(
+            "other".to_string(),
+            "password".to_string(),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(client.get_node(1).await.unwrap().name, "Room");
+
+    client.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn guard_ignores_fresh_cache_entry_of_changed_node() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 1, "name": "report.pdf", "type": "file", "updatedAt": "2021-01-01T12:00:00Z"
+        })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 1, "name": "report.pdf", "type": "file", "updatedAt": "2021-01-02T12:00:00Z"
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = connected_client(&server, Duration::from_secs(60)).await;
+
+    let cached = client.get_node(1).await.unwrap();
+    let expected_updated_at = cached.updated_at.unwrap();
+
+    // the node changed on the server while the cache entry is still fresh
+    assert_eq!(
+        client.get_node(1).await.unwrap().updated_at,
+        Some(expected_updated_at)
+    );
+    assert!(matches!(
+        client
+            .delete_node_if_unchanged(1, expected_updated_at)
+            .await,
+        Err(DRACOONClientError::Conflict)
+    ));
+
+    client.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn empty_room_lists_children_uncached() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(sample_node()))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [{ "id": 10, "name": "a.txt", "type": "file" }],
+            "range": { "offset": 0, "limit": 500, "total": 1 }
+        })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [
+                { "id": 10, "name": "a.txt", "type": "file" },
+                { "id": 11, "name": "b.txt", "type": "file" }
+            ],
+            "range": { "offset": 0, "limit": 500, "total": 2 }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v4/nodes"))
+        .and(body_json(json!({ "nodeIds": [10, 11] })))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = connected_client(&server, Duration::from_secs(60)).await;
+
+    // a child is added on the server while the cache entries are still fresh
+    assert!(client.get_node(1).await.unwrap().is_room());
+    assert_eq!(
+        client
+            .get_nodes(1, Some(0), None, None)
+            .await
+            .unwrap()
+            .items
+            .len(),
+        1
+    );
+
+    assert_eq!(client.empty_room(1).await.unwrap(), 2);
+
+    client.close().await.unwrap();
+}
@@ -0,0 +1,93 @@
+//! shared mock DRACOON server for integration tests - endpoint tests mount their own
+//! mocks on the server and use a connected client:
+//!
+//! ```ignore
+//! mod common;
+//!
+//! let server = MockServer::start().await;
+//! let client = common::connected_client(&server).await;
+//! ```
+
+#![allow(dead_code)]
+
+use dracoon_oxide::core::{DRACOONClient, DRACOONClientBuilder, OAuth2ConnectionType};
+use reqwest::Url;
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+pub const CLIENT_ID: &str = "client-id";
+pub const CLIENT_SECRET: &str = "client-secret";
+pub const ACCESS_TOKEN: &str = "access-token";
+pub const REFRESH_TOKEN: &str = "refresh-token";
+
+/// token response as returned by the OAuth token endpoint
+pub fn token_response(access_token: &str, refresh_token: &str) -> serde_json::Value {
+    json!({
+        "access_token": access_token,
+        "refresh_token": refresh_token,
+        "token_type": "bearer",
+        "expires_in": 28800,
+        "expires_in_inactive": 3600,
+        "scope": "all"
+    })
+}
+
+/// mounts the OAuth token endpoint (all grant types) and the revoke endpoint
+pub async fn mount_oauth(server: &MockServer) {
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(token_response(ACCESS_TOKEN, REFRESH_TOKEN)),
+        )
+        .mount(server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/revoke"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(server)
+        .await;
+}
+
+/// mounts the public software version endpoint with the given REST API version
+pub async fn mount_software_version(server: &MockServer, version: &str) {
+    Mock::given(method("GET"))
+        .and(path("/api/v4/public/software/version"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "restApiVersion": version,
+            "sdsServerVersion": version,
+            "buildDate": "2023-01-01"
+        })))
+        .mount(server)
+        .await;
+}
+
+/// client builder for the mock server
+pub fn builder(server: &MockServer) -> DRACOONClientBuilder {
+    DRACOONClient::builder(
+        Url::parse(&server.uri()).unwrap(),
+        CLIENT_ID.to_string(),
+        CLIENT_SECRET.to_string(),
+    )
+}
+
+/// connects a client (password flow) to the mock server
+pub async fn connect(server: &MockServer, mut client: DRACOONClient) -> DRACOONClient {
+    mount_oauth(server).await;
+
+    client
+        .connect(OAuth2ConnectionType::PasswordFlow(
+            "user".to_string(),
+            "password".to_string(),
+        ))
+        .await
+        .unwrap();
+
+    client
+}
+
+/// client with default settings connected to the mock server
+pub async fn connected_client(server: &MockServer) -> DRACOONClient {
+    connect(server, builder(server).build().unwrap()).await
+}
@@ -0,0 +1,58 @@
+use dracoon_oxide::config::ApiVersion;
+use dracoon_oxide::core::DRACOONClientError;
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+#[test]
+fn api_versions_are_parsed_and_ordered() {
+    let version: ApiVersion = "4.42.2".parse().unwrap();
+
+    assert_eq!(version, ApiVersion::new(4, 42, 2));
+    assert_eq!(
+        "4.30".parse::<ApiVersion>().unwrap(),
+        ApiVersion::new(4, 30, 0)
+    );
+    assert_eq!(
+        "4.43.0-beta.1".parse::<ApiVersion>().unwrap(),
+        ApiVersion::new(4, 43, 0)
+    );
+    assert!(ApiVersion::new(4, 9, 0) < ApiVersion::new(4, 10, 0));
+    assert!(matches!(
+        "latest".parse::<ApiVersion>(),
+        Err(DRACOONClientError::InvalidVersion(_))
+    ));
+}
+
+#[tokio::test]
+async fn requires_version_checks_the_server_version_once() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/public/software/version"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "restApiVersion": "4.40.1",
+            "sdsServerVersion": "4.40.1",
+            "buildDate": "2023-01-01"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::builder(&server).build().unwrap();
+
+    assert!(client
+        .requires_version(ApiVersion::new(4, 30, 0))
+        .await
+        .is_ok());
+
+    let res = client.requires_version(ApiVersion::new(4, 41, 0)).await;
+
+    assert!(matches!(
+        res,
+        Err(DRACOONClientError::FeatureUnavailable { available, .. })
+            if available == ApiVersion::new(4, 40, 1)
+    ));
+}
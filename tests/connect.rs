@@ -0,0 +1,585 @@
+use chrono::Utc;
+use dracoon_oxide::core::{
+    AuthMethod, DRACOONClientError, MockClock, OAuth2ConnectionType, RateLimitStatus,
+};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use wiremock::matchers::{body_string_contains, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+/// mounts the token endpoint for a single grant type (expected to be called once)
+async fn mount_token_grant(server: &MockServer, grant_type: &str) {
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .and(body_string_contains(format!("grant_type={}", grant_type)))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(common::token_response(
+                common::ACCESS_TOKEN,
+                common::REFRESH_TOKEN,
+            )),
+        )
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn connect_password_flow() {
+    let server = MockServer::start().await;
+    mount_token_grant(&server, "password").await;
+
+    let mut client = common::builder(&server).build().unwrap();
+
+    let info = client
+        .connect(OAuth2ConnectionType::PasswordFlow(
+            "user".to_string(),
+            "password".to_string(),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(info.auth_method, Some(AuthMethod::Password));
+    assert_eq!(info.scopes, vec!["all".to_string()]);
+    assert!(client.check_access_token_validity().unwrap());
+}
+
+#[tokio::test]
+async fn connect_auth_code_flow() {
+    let server = MockServer::start().await;
+    mount_token_grant(&server, "authorization_code").await;
+
+    let mut client = common::builder(&server).build().unwrap();
+
+    let info = client
+        .connect(OAuth2ConnectionType::AuthCode("auth-code".to_string()))
+        .await
+        .unwrap();
+
+    assert_eq!(info.auth_method, Some(AuthMethod::AuthCode));
+    assert!(client.check_access_token_validity().unwrap());
+}
+
+#[tokio::test]
+async fn connect_refresh_token_flow_after_disconnect() {
+    let server = MockServer::start().await;
+    mount_token_grant(&server, "refresh_token").await;
+
+    let client = common::connected_client(&server).await;
+    let mut client = client.disconnect(None).await.unwrap();
+
+    let info = client
+        .connect(OAuth2ConnectionType::RefreshToken)
+        .await
+        .unwrap();
+
+    assert_eq!(info.auth_method, Some(AuthMethod::RefreshToken));
+}
+
+#[tokio::test]
+async fn connect_refresh_token_flow_requires_connection() {
+    let server = MockServer::start().await;
+    let mut client = common::builder(&server).build().unwrap();
+
+    let res = client.connect(OAuth2ConnectionType::RefreshToken).await;
+
+    assert!(matches!(res, Err(DRACOONClientError::BrokenConnection)));
+}
+
+#[tokio::test]
+async fn test_connection_pings_authenticated() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/user/ping"))
+        .and(header(
+            "Authorization",
+            format!("Bearer {}", common::ACCESS_TOKEN).as_str(),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_string("test"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    assert!(client.test_connection().await.unwrap());
+}
+
+#[tokio::test]
+async fn check_access_token_validity_requires_connection() {
+    let server = MockServer::start().await;
+    let client = common::builder(&server).build().unwrap();
+
+    let res = client.check_access_token_validity();
+
+    assert!(matches!(res, Err(DRACOONClientError::BrokenConnection)));
+}
+
+#[tokio::test]
+async fn disconnect_revokes_access_token() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/revoke"))
+        .and(body_string_contains("token_type_hint=access_token"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .named("revoke access token")
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    assert!(client.disconnect(None).await.is_ok());
+}
+
+#[tokio::test]
+async fn connect_reports_token_lifetimes() {
+    let server = MockServer::start().await;
+    common::mount_oauth(&server).await;
+
+    let mut client = common::builder(&server).build().unwrap();
+
+    let info = client
+        .connect(OAuth2ConnectionType::PasswordFlow(
+            "user".to_string(),
+            "password".to_string(),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(info.expires_in, Duration::from_secs(28800));
+    assert_eq!(info.expires_in_inactive, Duration::from_secs(3600));
+    assert_eq!(
+        info.expires_at,
+        info.connected_at + chrono::Duration::seconds(28800)
+    );
+    assert!(info.inactivity_expires_at <= info.expires_at);
+    assert_eq!(info.token_type.as_deref(), Some("bearer"));
+}
+
+#[tokio::test]
+async fn connect_with_second_factor_completes_challenge() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .and(body_string_contains("otp=123456"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(common::token_response(
+                common::ACCESS_TOKEN,
+                common::REFRESH_TOKEN,
+            )),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(json!({
+            "code": 401,
+            "message": "Second factor required",
+            "errorCode": -10110
+        })))
+        .mount(&server)
+        .await;
+
+    let mut client = common::builder(&server).build().unwrap();
+    let flow = || OAuth2ConnectionType::PasswordFlow("user".to_string(), "password".to_string());
+
+    let err = client.connect(flow()).await.unwrap_err();
+    let challenge = err.second_factor_challenge().unwrap();
+
+    assert_eq!(challenge.message.as_deref(), Some("Second factor required"));
+
+    let info = client
+        .connect_with_second_factor(flow(), "123456")
+        .await
+        .unwrap();
+
+    assert_eq!(info.auth_method, Some(AuthMethod::Password));
+}
+
+#[tokio::test]
+async fn disconnect_without_connection_is_a_no_op() {
+    let server = MockServer::start().await;
+    let client = common::builder(&server).build().unwrap();
+
+    assert!(client.disconnect(None).await.is_ok());
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn disconnect_twice_revokes_once() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/revoke"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+    let client = client.disconnect(None).await.unwrap();
+
+    assert!(client.disconnect(None).await.is_ok());
+}
+
+/// connects with the password flow against a token endpoint returning the OAuth error
+async fn connect_with_oauth_error(error: &str) -> DRACOONClientError {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "error": error,
+            "error_description": "OAuth error"
+        })))
+        .mount(&server)
+        .await;
+
+    let mut client = common::builder(&server).build().unwrap();
+
+    client
+        .connect(OAuth2ConnectionType::PasswordFlow(
+            "user".to_string(),
+            "password".to_string(),
+        ))
+        .await
+        .unwrap_err()
+}
+
+#[tokio::test]
+async fn unsupported_grant_type_is_typed() {
+    match connect_with_oauth_error("unsupported_grant_type").await {
+        DRACOONClientError::UnsupportedGrantType {
+            grant_type, hint, ..
+        } => {
+            assert_eq!(grant_type, "password");
+            assert!(hint.contains("OAuth app"));
+        }
+        err => panic!("unexpected error: {:?}", err),
+    }
+}
+
+#[tokio::test]
+async fn unauthorized_client_is_unsupported_grant_type() {
+    let err = connect_with_oauth_error("unauthorized_client").await;
+
+    assert!(matches!(
+        err,
+        DRACOONClientError::UnsupportedGrantType { .. }
+    ));
+}
+
+#[tokio::test]
+async fn invalid_client_is_typed() {
+    let err = connect_with_oauth_error("invalid_client").await;
+
+    assert!(matches!(err, DRACOONClientError::InvalidClient(_)));
+}
+
+#[tokio::test]
+async fn invalid_grant_stays_generic() {
+    let err = connect_with_oauth_error("invalid_grant").await;
+
+    assert!(matches!(err, DRACOONClientError::DRACOONErrror(_)));
+}
+
+#[tokio::test]
+async fn request_counters_report_in_flight_and_total_requests() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/user/ping"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string("test")
+                .set_delay(Duration::from_millis(300)),
+        )
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    assert_eq!(client.in_flight_requests(), 0);
+
+    let (res, in_flight) = futures::join!(client.test_connection(), async {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        client.in_flight_requests()
+    });
+
+    assert!(res.unwrap());
+    assert_eq!(in_flight, 1);
+    assert_eq!(client.in_flight_requests(), 0);
+    assert_eq!(client.total_requests(), 1);
+
+    client.reset_counters();
+
+    assert_eq!(client.total_requests(), 0);
+}
+
+#[tokio::test]
+async fn mock_clock_expires_tokens_without_sleeping() {
+    let server = MockServer::start().await;
+    let clock = Arc::new(MockClock::new(Utc::now()));
+
+    let client = common::connect(
+        &server,
+        common::builder(&server)
+            .with_clock(clock.clone())
+            .build()
+            .unwrap(),
+    )
+    .await;
+
+    assert!(client.check_access_token_validity().unwrap());
+
+    clock.advance(chrono::Duration::seconds(3599));
+    assert!(client.check_access_token_validity().unwrap());
+
+    clock.advance(chrono::Duration::seconds(2));
+    assert!(!client.check_access_token_validity().unwrap());
+}
+
+#[tokio::test]
+async fn reconnect_if_needed_refreshes_expired_token_of_mock_clock() {
+    let server = MockServer::start().await;
+    mount_token_grant(&server, "refresh_token").await;
+
+    let clock = Arc::new(MockClock::new(Utc::now()));
+    let client = common::connect(
+        &server,
+        common::builder(&server)
+            .with_clock(clock.clone())
+            .build()
+            .unwrap(),
+    )
+    .await;
+
+    clock.advance(chrono::Duration::seconds(28801));
+    assert!(!client.check_access_token_validity().unwrap());
+
+    client.reconnect_if_needed().await.unwrap();
+
+    assert!(client.check_access_token_validity().unwrap());
+}
+
+#[tokio::test]
+async fn rate_limit_status_keeps_headers_of_last_response() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/user/ping"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string("test")
+                .insert_header("X-RateLimit-Limit", "100")
+                .insert_header("X-RateLimit-Remaining", "42")
+                .insert_header("X-RateLimit-Reset", "30"),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/user/ping"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string("test")
+                .insert_header("X-RateLimit-Remaining", "41")
+                .insert_header("X-RateLimit-Reset", "1700000000"),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/user/ping"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("test"))
+        .mount(&server)
+        .await;
+
+    let now = Utc::now();
+    let client = common::connect(
+        &server,
+        common::builder(&server)
+            .with_clock(Arc::new(MockClock::new(now)))
+            .build()
+            .unwrap(),
+    )
+    .await;
+
+    assert!(client.rate_limit_status().is_none());
+
+    client.test_connection().await.unwrap();
+
+    assert_eq!(
+        client.rate_limit_status(),
+        Some(RateLimitStatus {
+            limit: Some(100),
+            remaining: Some(42),
+            reset_at: Some(now + chrono::Duration::seconds(30)),
+            received_at: now,
+        })
+    );
+
+    client.test_connection().await.unwrap();
+
+    let status = client.rate_limit_status().unwrap();
+    assert_eq!(status.limit, None);
+    assert_eq!(status.remaining, Some(41));
+    assert_eq!(status.reset_at.unwrap().timestamp(), 1700000000);
+
+    // responses without rate limit headers keep the last status
+    client.test_connection().await.unwrap();
+
+    assert_eq!(client.rate_limit_status(), Some(status));
+}
+
+#[tokio::test]
+async fn clones_share_the_session_and_token_refresh() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .and(body_string_contains("grant_type=refresh_token"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(common::token_response(
+                "refreshed-token",
+                common::REFRESH_TOKEN,
+            )),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/user/ping"))
+        .and(header("Authorization", "Bearer refreshed-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("test"))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let clock = Arc::new(MockClock::new(Utc::now()));
+    let client = common::connect(
+        &server,
+        common::builder(&server)
+            .with_clock(clock.clone())
+            .build()
+            .unwrap(),
+    )
+    .await;
+    let clone = client.clone();
+
+    clock.advance(chrono::Duration::seconds(28801));
+    assert!(!client.check_access_token_validity().unwrap());
+
+    clone.reconnect_if_needed().await.unwrap();
+
+    assert!(client.check_access_token_validity().unwrap());
+    assert!(client.test_connection().await.unwrap());
+    assert!(clone.test_connection().await.unwrap());
+    assert_eq!(client.total_requests(), 2);
+
+    clone.set_dry_run(true);
+    assert!(client.is_dry_run());
+}
+
+#[tokio::test]
+async fn connect_and_close_reach_all_clones() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/revoke"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/user/ping"))
+        .and(header("Authorization", "Bearer access-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("test"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::builder(&server).build().unwrap();
+    // cloned before connecting: the clone sees the connection made afterwards
+    let clone = client.clone();
+    let client = common::connect(&server, client).await;
+
+    assert!(clone.connection_info().is_some());
+    assert!(clone.test_connection().await.unwrap());
+
+    // closing the clone ends the session of the client as well (revoked once)
+    clone.close().await.unwrap();
+    client.close().await.unwrap();
+}
+
+/// mounts a ping which is rejected once with 503 and the given Retry-After header
+async fn mount_unavailable_ping(server: &MockServer, retry_after: &str) {
+    Mock::given(method("GET"))
+        .and(path("/api/v4/user/ping"))
+        .respond_with(ResponseTemplate::new(503).insert_header("Retry-After", retry_after))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/user/ping"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("test"))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn retry_waits_for_retry_after_seconds() {
+    let server = MockServer::start().await;
+    mount_unavailable_ping(&server, "1").await;
+
+    let client = common::connected_client(&server).await;
+
+    let started = std::time::Instant::now();
+
+    assert!(client.test_connection().await.unwrap());
+    // longer than the first backoff step (500ms)
+    assert!(started.elapsed() >= Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn retry_waits_for_retry_after_date_of_client_clock() {
+    let server = MockServer::start().await;
+    // a day behind: the date is in the past for the system clock (no delay)
+    let now = Utc::now() - chrono::Duration::days(1);
+    let clock = Arc::new(MockClock::new(now));
+
+    let retry_at = (now + chrono::Duration::seconds(2))
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string();
+    mount_unavailable_ping(&server, &retry_at).await;
+
+    let client = common::connect(
+        &server,
+        common::builder(&server)
+            .with_clock(clock.clone())
+            .build()
+            .unwrap(),
+    )
+    .await;
+
+    let started = std::time::Instant::now();
+
+    assert!(client.test_connection().await.unwrap());
+    assert!(started.elapsed() >= Duration::from_secs(1));
+    assert!(started.elapsed() < Duration::from_secs(10));
+}
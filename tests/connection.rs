@@ -0,0 +1,90 @@
+use chrono::{Duration, Utc};
+use dracoon_oxide::core::{
+    DRACOONClient, DRACOONConnection, MockClock, OAuth2TokenResponse, SerializedConnection,
+};
+use serde_json::json;
+
+fn sample_token_response() -> OAuth2TokenResponse {
+    serde_json::from_value(json!({
+        "access_token": "access-token",
+        "refresh_token": "refresh-token",
+        "token_type": "bearer",
+        "expires_in": 28800,
+        "expires_in_inactive": 3600,
+        "scope": "all"
+    }))
+    .unwrap()
+}
+
+#[test]
+fn access_token_validity_uses_expires_in() {
+    let conn = DRACOONConnection::from(&sample_token_response());
+
+    assert_eq!(conn.access_token_validity(), 28800);
+    assert_eq!(conn.refresh_token_validity(), 3600);
+}
+
+#[test]
+fn absolute_expiry_is_connected_at_plus_expires_in() {
+    let conn = DRACOONConnection::from(&sample_token_response());
+
+    assert_eq!(
+        conn.absolute_expiry(),
+        conn.connected_at() + Duration::seconds(28800)
+    );
+}
+
+#[test]
+fn connection_is_connected_at_time_of_given_clock() {
+    let now = Utc::now() - Duration::days(1);
+    let conn = DRACOONConnection::new(&sample_token_response(), &MockClock::new(now));
+
+    assert_eq!(conn.connected_at(), now);
+    assert_eq!(conn.last_activity(), now);
+    assert_eq!(conn.absolute_expiry(), now + Duration::seconds(28800));
+}
+
+#[test]
+fn inactivity_expiry_is_last_activity_plus_expires_in_inactive() {
+    let conn = DRACOONConnection::from(&sample_token_response());
+
+    assert_eq!(
+        conn.inactivity_expiry(),
+        conn.last_activity() + Duration::seconds(3600)
+    );
+}
+
+#[test]
+fn connection_is_valid_until_first_expiry() {
+    let conn = DRACOONConnection::from(&sample_token_response());
+    let now = Utc::now();
+
+    assert!(conn.is_valid_at(now));
+    assert!(!conn.is_valid_at(now + Duration::seconds(3601)));
+    assert!(!conn.is_valid_at(now + Duration::seconds(28801)));
+}
+
+#[test]
+fn has_scope_checks_granted_scopes() {
+    let mut client = DRACOONClient::new(
+        "https://dracoon.example.com/",
+        "client-id".to_string(),
+        "client-secret".to_string(),
+    )
+    .unwrap();
+
+    assert!(!client.has_scope("manage_dl_share"));
+
+    client.import_connection(SerializedConnection {
+        access_token: "access-token".to_string(),
+        refresh_token: "refresh-token".to_string(),
+        connected_at: Utc::now(),
+        last_activity: Utc::now(),
+        expires_in: 28800,
+        expires_in_inactive: 3600,
+        scopes: vec!["manage_dl_share".to_string()],
+    });
+
+    assert!(client.has_scope("manage_dl_share"));
+    assert!(!client.has_scope("manage_ul_share"));
+}
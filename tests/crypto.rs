@@ -0,0 +1,45 @@
+use dracoon_oxide::crypto::{FileEncryptor, PlainFileKey};
+
+/// AES-256-GCM reference (key 0..32, IV 0..12, no additional data)
+fn file_key() -> PlainFileKey {
+    PlainFileKey::new((0..32).collect(), &(0..12).collect::<Vec<u8>>()).unwrap()
+}
+
+#[test]
+fn file_encryptor_matches_aes_gcm_across_chunks() {
+    let plain: Vec<u8> = (0..100u32).map(|i| (i * 7 % 256) as u8).collect();
+
+    let mut encryptor = FileEncryptor::new(&file_key()).unwrap();
+
+    // chunk borders within GCM blocks
+    let mut encrypted = encryptor.encrypt(&plain[..7]);
+    encrypted.extend(encryptor.encrypt(&plain[7..40]));
+    encrypted.extend(encryptor.encrypt(&plain[40..]));
+
+    assert_eq!(
+        encrypted,
+        hex("4705d80ed9c6e82ab57ed1c6e5b21a04f3a1f9b17ce8c5dd90c85338d9a2d26be1f7400953c218896cbb59c0bcbc6a71be0e3ee836a5d95bb718bc84bc48475730fb08af0f32cc90e4abac63fb942cc108bdf7273a3009099592e8b1fc7b2b146b5ff753")
+    );
+    assert_eq!(encryptor.finalize(), "4tolU7jQIDxy2fxN3YSjRw==");
+}
+
+#[test]
+fn file_encryptor_tags_empty_files() {
+    let encryptor = FileEncryptor::new(&file_key()).unwrap();
+
+    assert_eq!(encryptor.finalize(), "9MLbHcOIBaN7khccXQqBzA==");
+}
+
+#[test]
+fn file_keys_require_aes_256_and_96_bit_iv() {
+    assert!(PlainFileKey::new(vec![0; 16], &[0; 12]).is_err());
+    assert!(PlainFileKey::new(vec![0; 32], &[0; 16]).is_err());
+    assert!(FileEncryptor::new(&PlainFileKey::generate()).is_ok());
+}
+
+fn hex(value: &str) -> Vec<u8> {
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap())
+        .collect()
+}
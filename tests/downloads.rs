@@ -0,0 +1,308 @@
+use chrono::{DateTime, Utc};
+use dracoon_oxide::core::{DRACOONClientError, MockClock};
+use dracoon_oxide::downloads::DownloadOptions;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+#[tokio::test]
+async fn cross_host_download_does_not_send_bearer_token() {
+    let dracoon = MockServer::start().await;
+    let storage = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/nodes/files/1/downloads"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "downloadUrl": format!("{}/bucket/file?signature=abc", storage.uri())
+        })))
+        .mount(&dracoon)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/bucket/file"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("hello"))
+        .mount(&storage)
+        .await;
+
+    let client = common::connected_client(&dracoon).await;
+
+    let mut content = Vec::new();
+    let written = client.download(1, &mut content).await.unwrap();
+
+    assert_eq!(written, 5);
+    assert_eq!(content, b"hello");
+
+    let requests = storage.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1);
+    assert!(!requests[0].headers.contains_key("authorization"));
+
+    client.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn same_host_download_sends_bearer_token() {
+    let dracoon = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/nodes/files/1/downloads"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "downloadUrl": format!("{}/api/v4/downloads/token", dracoon.uri())
+        })))
+        .mount(&dracoon)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/downloads/token"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("hello"))
+        .mount(&dracoon)
+        .await;
+
+    let client = common::connected_client(&dracoon).await;
+
+    let mut content = Vec::new();
+    client.download(1, &mut content).await.unwrap();
+
+    let requests = dracoon.received_requests().await.unwrap();
+    let download = requests
+        .iter()
+        .find(|req| req.url.path() == "/api/v4/downloads/token")
+        .unwrap();
+    assert_eq!(
+        download.headers.get("authorization").unwrap(),
+        "Bearer access-token"
+    );
+
+    client.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn direct_download_link_reads_presigned_expiry() {
+    let dracoon = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/nodes/files/1/downloads"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "downloadUrl": "https://s3.example.com/bucket/file?X-Amz-Date=20240101T120000Z&X-Amz-Expires=3600&X-Amz-Signature=abc"
+        })))
+        .mount(&dracoon)
+        .await;
+
+    let client = common::connected_client(&dracoon).await;
+
+    let link = client
+        .create_direct_download_link(1, Duration::from_secs(7200))
+        .await
+        .unwrap();
+
+    assert!(link.url.starts_with("https://s3.example.com/bucket/file"));
+    assert_eq!(link.expires_at.to_rfc3339(), "2024-01-01T13:00:00+00:00");
+
+    client.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn direct_download_link_expires_after_ttl_of_client_clock() {
+    let dracoon = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/nodes/files/1/downloads"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "downloadUrl": "https://s3.example.com/bucket/file?X-Amz-Date=20240101T120000Z&X-Amz-Expires=3600&X-Amz-Signature=abc"
+        })))
+        .mount(&dracoon)
+        .await;
+
+    let now = DateTime::parse_from_rfc3339("2024-01-01T12:00:00Z").unwrap();
+    let client = common::builder(&dracoon)
+        .with_clock(Arc::new(MockClock::new(now.with_timezone(&Utc))))
+        .build()
+        .unwrap();
+    let client = common::connect(&dracoon, client).await;
+
+    let link = client
+        .create_direct_download_link(1, Duration::from_secs(600))
+        .await
+        .unwrap();
+
+    assert_eq!(link.expires_at.to_rfc3339(), "2024-01-01T12:10:00+00:00");
+
+    client.close().await.unwrap();
+}
+
+/// mounts a download url and a file content response delayed by the given time
+async fn mount_delayed_download(server: &MockServer, delay: Duration) {
+    Mock::given(method("POST"))
+        .and(path("/api/v4/nodes/files/1/downloads"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "downloadUrl": format!("{}/api/v4/downloads/token", server.uri())
+        })))
+        .mount(server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/downloads/token"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string("hello")
+                .set_delay(delay),
+        )
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn download_timeout_overrides_client_timeout() {
+    let server = MockServer::start().await;
+    mount_delayed_download(&server, Duration::from_millis(500)).await;
+
+    let client = common::builder(&server)
+        .with_timeout(Duration::from_millis(200))
+        .with_max_retries(0)
+        .build()
+        .unwrap();
+    let client = common::connect(&server, client).await;
+
+    let options = DownloadOptions {
+        timeout: Some(Duration::from_secs(5)),
+        ..Default::default()
+    };
+
+    let mut content = Vec::new();
+    let written = client
+        .download_with(1, &mut content, options)
+        .await
+        .unwrap();
+
+    assert_eq!(written, 5);
+}
+
+#[tokio::test]
+async fn download_returns_timeout_once_exceeded() {
+    let server = MockServer::start().await;
+    mount_delayed_download(&server, Duration::from_secs(5)).await;
+
+    let client = common::connected_client(&server).await;
+
+    let options = DownloadOptions {
+        timeout: Some(Duration::from_millis(200)),
+        ..Default::default()
+    };
+
+    let mut content = Vec::new();
+    let res = client.download_with(1, &mut content, options).await;
+
+    assert!(matches!(res, Err(DRACOONClientError::Timeout)));
+}
+
+/// file node 1 (5 bytes) with the given hash
+fn file_node(hash: &str) -> Mock {
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 1, "name": "file.txt", "type": "file", "size": 5, "hash": hash
+        })))
+}
+
+/// mounts a download url - the first GET is cut short after 3 bytes
+async fn mount_interrupted_download(server: &MockServer) {
+    Mock::given(method("POST"))
+        .and(path("/api/v4/nodes/files/1/downloads"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "downloadUrl": format!("{}/api/v4/downloads/token", server.uri())
+        })))
+        .mount(server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/downloads/token"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("hel"))
+        .up_to_n_times(1)
+        .mount(server)
+        .await;
+}
+
+fn download_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_file(partial_path(&path));
+
+    path
+}
+
+fn partial_path(path: &Path) -> PathBuf {
+    PathBuf::from(format!("{}.dracoon-partial", path.display()))
+}
+
+#[tokio::test]
+async fn download_resumable_resumes_unchanged_file() {
+    let server = MockServer::start().await;
+    file_node("abc").mount(&server).await;
+    mount_interrupted_download(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/downloads/token"))
+        .and(header("range", "bytes=3-"))
+        .respond_with(ResponseTemplate::new(206).set_body_string("lo"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+    let path = download_path("resumes-unchanged.txt");
+
+    assert!(matches!(
+        client.download_resumable(1, &path).await,
+        Err(DRACOONClientError::SizeMismatch {
+            expected: 5,
+            actual: 3
+        })
+    ));
+    assert!(partial_path(&path).exists());
+
+    assert_eq!(client.download_resumable(1, &path).await.unwrap(), 2);
+    assert_eq!(std::fs::read(&path).unwrap(), b"hello");
+    assert!(!partial_path(&path).exists());
+
+    std::fs::remove_file(&path).unwrap();
+    client.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn download_resumable_restarts_if_hash_changed() {
+    let server = MockServer::start().await;
+    file_node("abc").up_to_n_times(1).mount(&server).await;
+    file_node("def").mount(&server).await;
+    mount_interrupted_download(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/downloads/token"))
+        .and(header("range", "bytes=3-"))
+        .respond_with(ResponseTemplate::new(206).set_body_string("LO"))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/downloads/token"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("HELLO"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+    let path = download_path("restarts-changed.txt");
+
+    assert!(client.download_resumable(1, &path).await.is_err());
+
+    // the file is replaced server-side with the same size
+    assert_eq!(client.download_resumable(1, &path).await.unwrap(), 5);
+    assert_eq!(std::fs::read(&path).unwrap(), b"HELLO");
+
+    std::fs::remove_file(&path).unwrap();
+    client.close().await.unwrap();
+}
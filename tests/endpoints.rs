@@ -0,0 +1,57 @@
+use dracoon_oxide::endpoints::Endpoints;
+use reqwest::Url;
+
+fn endpoints(base_url: &str) -> Endpoints {
+    Endpoints::new(&Url::parse(base_url).unwrap())
+}
+
+#[test]
+fn endpoints_are_built_from_base_url() {
+    let endpoints = endpoints("https://dracoon.example.com");
+
+    assert_eq!(endpoints.token(), "https://dracoon.example.com/oauth/token");
+    assert_eq!(
+        endpoints.revoke(),
+        "https://dracoon.example.com/oauth/revoke"
+    );
+    assert_eq!(
+        endpoints.api("user/account"),
+        "https://dracoon.example.com/api/v4/user/account"
+    );
+    assert_eq!(
+        endpoints.node(42),
+        "https://dracoon.example.com/api/v4/nodes/42"
+    );
+    assert_eq!(
+        endpoints.public_download_share("key"),
+        "https://dracoon.example.com/api/v4/public/shares/downloads/key"
+    );
+    assert_eq!(
+        endpoints.public_upload_link("key"),
+        "https://dracoon.example.com/public/upload-shares/key"
+    );
+    assert_eq!(
+        endpoints.media_server("image/token/200x200"),
+        "https://dracoon.example.com/mediaserver/image/token/200x200"
+    );
+}
+
+#[test]
+fn endpoints_keep_base_path_without_double_slashes() {
+    let with_slash = endpoints("https://example.com/dracoon/");
+    let without_slash = endpoints("https://example.com/dracoon");
+
+    assert_eq!(with_slash, without_slash);
+    assert_eq!(
+        without_slash.api("/nodes"),
+        "https://example.com/dracoon/api/v4/nodes"
+    );
+    assert_eq!(
+        without_slash.authorize().as_str(),
+        "https://example.com/dracoon/oauth/authorize"
+    );
+    assert_eq!(
+        without_slash.redirect(),
+        "https://example.com/dracoon/oauth/callback"
+    );
+}
@@ -0,0 +1,168 @@
+use dracoon_oxide::core::{DRACOONClient, DRACOONClientError, DryRunAction, SerializedConnection};
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+async fn get_node_with_412(error_code: i32) -> DRACOONClientError {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(ResponseTemplate::new(412).set_body_json(json!({
+            "code": 412,
+            "message": "Precondition failed",
+            "debugInfo": "Precondition failed",
+            "errorCode": error_code
+        })))
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+    let err = client.get_node(1).await.unwrap_err();
+    client.close().await.unwrap();
+
+    err
+}
+
+#[tokio::test]
+async fn precondition_keypair_missing_is_typed() {
+    let err = get_node_with_412(-70020).await;
+
+    assert!(matches!(err, DRACOONClientError::KeypairMissing(error) if error.code == Some(412)));
+}
+
+#[tokio::test]
+async fn precondition_second_factor_required_is_typed() {
+    let err = get_node_with_412(-10109).await;
+
+    assert!(matches!(err, DRACOONClientError::SecondFactorRequired(_)));
+}
+
+#[tokio::test]
+async fn unknown_precondition_stays_generic() {
+    let err = get_node_with_412(-10103).await;
+
+    assert!(
+        matches!(err, DRACOONClientError::DRACOONErrror(error) if error.error_code == Some(-10103))
+    );
+}
+
+#[tokio::test]
+async fn oversized_response_body_is_rejected() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 1,
+            "name": "x".repeat(2048),
+            "type": "room"
+        })))
+        .mount(&server)
+        .await;
+
+    let mut client = DRACOONClient::builder(
+        reqwest::Url::parse(&server.uri()).unwrap(),
+        "client-id".to_string(),
+        "client-secret".to_string(),
+    )
+    .with_max_response_body_size(1024)
+    .build()
+    .unwrap();
+
+    client.import_connection(SerializedConnection {
+        access_token: "access-token".to_string(),
+        refresh_token: "refresh-token".to_string(),
+        connected_at: chrono::Utc::now(),
+        last_activity: chrono::Utc::now(),
+        expires_in: 28800,
+        expires_in_inactive: 3600,
+        scopes: vec!["all".to_string()],
+    });
+
+    let res = client.get_node(1).await;
+
+    assert!(matches!(res, Err(DRACOONClientError::ResponseTooLarge)));
+}
+
+#[tokio::test]
+async fn dry_run_skips_delete_but_executes_reads() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({ "id": 1, "name": "Room", "type": "room" })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+    client.set_dry_run(true);
+
+    assert_eq!(client.get_node(1).await.unwrap().name, "Room");
+    assert!(matches!(
+        client.delete_node(1).await,
+        Err(DRACOONClientError::DryRun(DryRunAction::DeleteNode {
+            node_id: 1
+        }))
+    ));
+
+    client.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn json_with_charset_and_bom_is_parsed() {
+    let server = MockServer::start().await;
+
+    let mut body = b"\xEF\xBB\xBF".to_vec();
+    body.extend_from_slice(br#"{ "id": 1, "name": "Room", "type": "room" }"#);
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_raw(body, "application/json; charset=UTF-8"),
+        )
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    let node = client.get_node(1).await.unwrap();
+
+    assert_eq!(node.name, "Room");
+}
+
+#[tokio::test]
+async fn latin1_error_body_is_decoded() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(ResponseTemplate::new(400).set_body_raw(
+            b"{ \"code\": 400, \"message\": \"Ung\xFCltiger Name\" }".to_vec(),
+            "application/json;charset=\"ISO-8859-1\"",
+        ))
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    match client.get_node(1).await {
+        Err(DRACOONClientError::DRACOONErrror(error)) => {
+            assert_eq!(error.message.as_deref(), Some("Ungültiger Name"))
+        }
+        res => panic!("unexpected result: {:?}", res.map(|node| node.id)),
+    }
+}
@@ -0,0 +1,60 @@
+use chrono::{Duration, Utc};
+use dracoon_oxide::core::DRACOONClient;
+use futures::StreamExt;
+use serde_json::json;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+async fn connected_client(server: &MockServer, page_size: u64) -> DRACOONClient {
+    let client = common::builder(server)
+        .with_default_page_size(page_size)
+        .build()
+        .unwrap();
+
+    common::connect(server, client).await
+}
+
+fn event(id: u64) -> serde_json::Value {
+    json!({ "id": id, "time": "2024-01-01T00:00:00Z", "userId": 1, "status": 0 })
+}
+
+#[tokio::test]
+async fn audit_event_stream_fetches_all_pages() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/eventlog/events"))
+        .and(query_param("offset", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "range": { "offset": 0, "limit": 2, "total": 3 },
+            "items": [event(1), event(2)]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/eventlog/events"))
+        .and(query_param("offset", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "range": { "offset": 2, "limit": 2, "total": 3 },
+            "items": [event(3)]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = connected_client(&server, 2).await;
+
+    let ids: Vec<u64> = client
+        .stream_audit_events(Utc::now() - Duration::days(1), Utc::now())
+        .map(|event| event.unwrap().id)
+        .collect()
+        .await;
+
+    assert_eq!(ids, vec![1, 2, 3]);
+
+    client.close().await.unwrap();
+}
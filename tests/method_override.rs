@@ -0,0 +1,69 @@
+use serde_json::json;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+#[tokio::test]
+async fn method_override_tunnels_delete_as_post() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/nodes/1"))
+        .and(header("X-HTTP-Method-Override", "DELETE"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::builder(&server)
+        .with_method_override(true)
+        .build()
+        .unwrap();
+    let client = common::connect(&server, client).await;
+
+    assert!(client.config().method_override);
+    client.delete_node(1).await.unwrap();
+}
+
+#[tokio::test]
+async fn method_override_keeps_get_requests() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 1,
+            "name": "room",
+            "type": "room"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::builder(&server)
+        .with_method_override(true)
+        .build()
+        .unwrap();
+    let client = common::connect(&server, client).await;
+
+    let node = client.get_node(1).await.unwrap();
+
+    assert_eq!(node.id, 1);
+}
+
+#[tokio::test]
+async fn delete_is_sent_as_is_by_default() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    client.delete_node(1).await.unwrap();
+}
@@ -0,0 +1,291 @@
+use dracoon_oxide::account::{CustomerInfo, NotificationConfig, UserAccount};
+use dracoon_oxide::config::{PasswordPolicies, SoftwareVersion};
+use dracoon_oxide::core::{DRACOONErrorResponse, OAuth2TokenResponse, Paged};
+use dracoon_oxide::eventlog::LogEvent;
+use dracoon_oxide::groups::Group;
+use dracoon_oxide::nodes::Node;
+use dracoon_oxide::recyclebin::DeletedNode;
+use dracoon_oxide::shares::{DownloadShare, PublicDownloadShare, UploadShare};
+use dracoon_oxide::subscriptions::Subscription;
+use dracoon_oxide::uploads::PendingUpload;
+use dracoon_oxide::users::UserItem;
+use dracoon_oxide::webhooks::Webhook;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// asserts that every field of the expected payload is present with the same value in the
+/// actual one (nested objects and arrays included) - fields not in the payload are ignored
+fn assert_contains(expected: &Value, actual: &Value, field: &str) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, value) in expected {
+                let actual = actual
+                    .get(key)
+                    .unwrap_or_else(|| panic!("field {}.{} missing", field, key));
+                assert_contains(value, actual, &format!("{}.{}", field, key));
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) => {
+            assert_eq!(expected.len(), actual.len(), "length of {}", field);
+
+            for (i, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+                assert_contains(expected, actual, &format!("{}[{}]", field, i));
+            }
+        }
+        _ => assert_eq!(expected, actual, "value of {}", field),
+    }
+}
+
+/// deserializes a captured payload into the model, serializes it again and checks that
+/// all fields of the payload survived - returns the model for further checks
+fn round_trip<T: Serialize + DeserializeOwned>(payload: Value) -> T {
+    let model: T = serde_json::from_value(payload.clone()).unwrap();
+    let serialized = serde_json::to_value(&model).unwrap();
+
+    assert_contains(&payload, &serialized, "$");
+
+    model
+}
+
+#[test]
+fn node_round_trip() {
+    let node: Node = round_trip(json!({
+        "id": 42,
+        "name": "report.pdf",
+        "type": "file",
+        "parentId": 7,
+        "parentPath": "/Room/Folder/",
+        "authParentId": 3,
+        "size": 1024,
+        "isEncrypted": false,
+        "createdAt": "2021-11-02T10:15:00Z",
+        "updatedAt": "2021-11-03T08:00:00Z",
+        "expireAt": "2022-01-01T00:00:00Z",
+        "hash": "d41d8cd98f00b204e9800998ecf8427e",
+        "mediaToken": "media-token",
+        "createdBy": {
+            "id": 1,
+            "userName": "jdoe",
+            "firstName": "Jane",
+            "lastName": "Doe",
+            "email": "jane.doe@example.com"
+        }
+    }));
+
+    assert_eq!(node.created_by.unwrap().user_name.as_deref(), Some("jdoe"));
+}
+
+#[test]
+fn node_list_round_trip() {
+    let nodes: Paged<Node> = round_trip(json!({
+        "range": { "offset": 0, "limit": 500, "total": 2 },
+        "items": [
+            { "id": 1, "name": "Room", "type": "room" },
+            { "id": 2, "name": "Folder", "type": "folder", "parentId": 1 }
+        ]
+    }));
+
+    assert_eq!(nodes.range.total, 2);
+}
+
+#[test]
+fn user_models_round_trip() {
+    round_trip::<UserItem>(json!({
+        "id": 5,
+        "userName": "jdoe",
+        "firstName": "Jane",
+        "lastName": "Doe",
+        "email": "jane.doe@example.com",
+        "isLocked": false,
+        "expireAt": "2022-06-30T00:00:00Z",
+        "lastLoginAt": "2021-11-01T07:30:00Z"
+    }));
+
+    round_trip::<UserAccount>(json!({
+        "id": 5,
+        "userName": "jdoe",
+        "firstName": "Jane",
+        "lastName": "Doe",
+        "email": "jane.doe@example.com",
+        "language": "de-DE",
+        "isLocked": false,
+        "isEncryptionEnabled": true,
+        "hasManageableRooms": true,
+        "homeRoomId": 9,
+        "lastLoginSuccessfulAt": "2021-11-01T07:30:00Z",
+        "userRoles": {
+            "items": [{ "id": 1, "name": "CONFIG_MANAGER", "description": "Manages global configs" }]
+        }
+    }));
+
+    round_trip::<Group>(json!({
+        "id": 3,
+        "name": "Team",
+        "cntUsers": 4,
+        "createdAt": "2021-01-01T00:00:00Z",
+        "updatedAt": "2021-02-01T00:00:00Z",
+        "expireAt": "2022-01-01T00:00:00Z"
+    }));
+}
+
+#[test]
+fn account_models_round_trip() {
+    round_trip::<CustomerInfo>(json!({
+        "id": 1,
+        "name": "Example Inc.",
+        "spaceLimit": 10737418240i64,
+        "spaceUsed": 1073741824,
+        "accountsLimit": 100,
+        "accountsUsed": 12
+    }));
+
+    round_trip::<NotificationConfig>(json!({
+        "id": 2,
+        "eventGroupName": "file.created",
+        "channelIds": [1, 2]
+    }));
+}
+
+#[test]
+fn share_models_round_trip() {
+    round_trip::<DownloadShare>(json!({
+        "id": 11,
+        "name": "report.pdf",
+        "nodeId": 42,
+        "accessKey": "access-key",
+        "isProtected": true,
+        "expireAt": "2022-01-01T00:00:00Z",
+        "createdAt": "2021-11-02T10:15:00Z",
+        "cntDownloads": 3,
+        "maxDownloads": 10
+    }));
+
+    round_trip::<UploadShare>(json!({
+        "id": 12,
+        "name": "Inbox",
+        "targetId": 7,
+        "accessKey": "access-key",
+        "isProtected": false,
+        "createdAt": "2021-11-02T10:15:00Z",
+        "cntFiles": 5
+    }));
+
+    round_trip::<PublicDownloadShare>(json!({
+        "isProtected": true,
+        "fileName": "report.pdf",
+        "size": 1024,
+        "limitReached": false,
+        "creatorName": "Jane Doe",
+        "createdAt": "2021-11-02T10:15:00Z",
+        "mediaType": "application/pdf",
+        "isEncrypted": false
+    }));
+}
+
+#[test]
+fn misc_models_round_trip() {
+    round_trip::<Subscription>(json!({ "nodeId": 42, "authParentId": 3 }));
+
+    round_trip::<LogEvent>(json!({
+        "id": 100,
+        "time": "2021-11-02T10:15:00Z",
+        "userId": 5,
+        "userName": "jdoe",
+        "message": "File uploaded",
+        "operationId": 8,
+        "operationName": "Upload file",
+        "status": 0,
+        "userClient": "dracoon-oxide",
+        "userIp": "10.0.0.1",
+        "objectName1": "report.pdf",
+        "objectId1": 42
+    }));
+
+    round_trip::<Webhook>(json!({
+        "id": 1,
+        "name": "hook",
+        "url": "https://hooks.example.com",
+        "isEnabled": true,
+        "eventTypeNames": ["file.created"],
+        "failStatus": 502
+    }));
+
+    round_trip::<DeletedNode>(json!({
+        "id": 77,
+        "parentId": 7,
+        "type": "file",
+        "name": "report.pdf",
+        "size": 512,
+        "deletedAt": "2021-11-04T09:00:00Z"
+    }));
+
+    round_trip::<PendingUpload>(json!({
+        "uploadId": "upload-id",
+        "parentId": 7,
+        "name": "report.pdf",
+        "createdAt": "2021-11-04T09:00:00Z"
+    }));
+}
+
+#[test]
+fn config_models_round_trip() {
+    round_trip::<SoftwareVersion>(json!({
+        "restApiVersion": "4.42.2",
+        "sdsServerVersion": "4.42.2",
+        "buildDate": "2023-01-01",
+        "isDracoonCloud": true
+    }));
+
+    round_trip::<PasswordPolicies>(json!({
+        "loginPasswordPolicies": {
+            "minLength": 12,
+            "characterRules": {
+                "mustContainCharacters": ["uppercase", "numeric"],
+                "numberOfCharacteristicsToEnforce": 2
+            },
+            "rejectDictionaryWords": true,
+            "rejectUserInfo": true,
+            "rejectKeyboardPatterns": false
+        }
+    }));
+}
+
+#[test]
+fn token_response_round_trip() {
+    round_trip::<OAuth2TokenResponse>(json!({
+        "access_token": "access-token",
+        "refresh_token": "refresh-token",
+        "token_type": "bearer",
+        "expires_in": 28800,
+        "expires_in_inactive": 3600,
+        "scope": "all"
+    }));
+}
+
+#[test]
+fn error_responses_keep_api_and_oauth_fields() {
+    let api: DRACOONErrorResponse = serde_json::from_value(json!({
+        "code": 412,
+        "message": "Precondition failed",
+        "debugInfo": "no key pair",
+        "errorCode": -70020
+    }))
+    .unwrap();
+
+    assert_eq!(api.code, Some(412));
+    assert_eq!(api.debug_info.as_deref(), Some("no key pair"));
+    assert_eq!(api.error_code, Some(-70020));
+
+    let oauth: DRACOONErrorResponse = serde_json::from_value(json!({
+        "error": "invalid_grant",
+        "error_description": "Invalid refresh token"
+    }))
+    .unwrap();
+
+    assert_eq!(oauth.error.as_deref(), Some("invalid_grant"));
+    assert_eq!(
+        oauth.error_description.as_deref(),
+        Some("Invalid refresh token")
+    );
+}
@@ -0,0 +1,450 @@
+use dracoon_oxide::core::{DRACOONClientError, DryRunAction, Paged};
+use dracoon_oxide::nodes::{Node, NodeCounts, UpdateRoomPoliciesRequest};
+use dracoon_oxide::uploads::ResolutionStrategy;
+use futures::TryStreamExt;
+use serde_json::json;
+use wiremock::matchers::{body_json, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+#[test]
+fn node_listing_counts_nodes_by_type() {
+    let nodes: Paged<Node> = serde_json::from_value(json!({
+        "range": { "offset": 0, "limit": 500, "total": 5 },
+        "items": [
+            { "id": 1, "name": "Folder A", "type": "folder" },
+            { "id": 2, "name": "Folder B", "type": "folder" },
+            { "id": 3, "name": "report.pdf", "type": "file" },
+            { "id": 4, "name": "Room", "type": "room" },
+            { "id": 5, "name": "future", "type": "shortcut" }
+        ]
+    }))
+    .unwrap();
+
+    assert_eq!(
+        nodes.count_by_type(),
+        NodeCounts {
+            rooms: 1,
+            folders: 2,
+            files: 1,
+            other: 1,
+        }
+    );
+}
+
+#[test]
+fn node_exposes_creator_and_editor() {
+    let node: Node = serde_json::from_value(json!({
+        "id": 1,
+        "name": "report.pdf",
+        "type": "file",
+        "createdBy": { "id": 2, "firstName": "Jane", "lastName": "Doe", "userName": "jdoe" },
+        "updatedBy": { "id": 3, "userName": "service" }
+    }))
+    .unwrap();
+
+    assert_eq!(node.created_by_name().as_deref(), Some("Jane Doe"));
+    assert_eq!(node.updated_by_name().as_deref(), Some("service"));
+
+    let node: Node = serde_json::from_value(json!({
+        "id": 1,
+        "name": "Room",
+        "type": "room",
+        "createdBy": null
+    }))
+    .unwrap();
+
+    assert!(node.created_by.is_none());
+    assert!(node.updated_by_name().is_none());
+}
+
+/// list response with the given number of nodes (long names for large bodies)
+fn node_page(offset: u64, count: u64, total: u64) -> serde_json::Value {
+    let items: Vec<serde_json::Value> = (offset..offset + count)
+        .map(|id| {
+            json!({
+                "id": id,
+                "name": format!("{} \"items\": [{{}}] {}", id, "x".repeat(600)),
+                "type": "file",
+                "parentId": 1
+            })
+        })
+        .collect();
+
+    json!({
+        "items": items,
+        "range": { "offset": offset, "limit": 500, "total": total }
+    })
+}
+
+#[tokio::test]
+async fn stream_nodes_reads_large_and_small_pages() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes"))
+        .and(query_param("offset", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(node_page(0, 500, 503)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes"))
+        .and(query_param("offset", "500"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(node_page(500, 3, 503)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    let nodes: Vec<Node> = client.stream_nodes(1, None).try_collect().await.unwrap();
+
+    assert_eq!(nodes.len(), 503);
+    assert!(nodes
+        .iter()
+        .enumerate()
+        .all(|(i, node)| node.id == i as u64));
+    assert!(nodes[0].name.starts_with("0 \"items\": [{}] x"));
+}
+
+#[tokio::test]
+async fn patch_node_sends_raw_fields() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 7, "name": "report.pdf", "type": "file", "parentId": 1
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/nodes/files/7"))
+        .and(body_json(json!({ "classification": 3 })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 7, "name": "report.pdf", "type": "file", "parentId": 1
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    let node = client
+        .patch_node(7, json!({ "classification": 3 }))
+        .await
+        .unwrap();
+
+    assert_eq!(node.id, 7);
+}
+
+#[tokio::test]
+async fn patch_node_rejects_non_object_body() {
+    let server = MockServer::start().await;
+    let client = common::connected_client(&server).await;
+
+    let res = client.patch_node(7, json!(["classification", 3])).await;
+
+    assert!(matches!(
+        res,
+        Err(DRACOONClientError::InvalidRequestBody(_))
+    ));
+    assert!(server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .all(|req| req.url.path() == "/oauth/token"));
+}
+
+/// mounts the node of the given type as node 1
+async fn mount_node_1(server: &MockServer, node_type: &str) {
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 1,
+            "name": "Room",
+            "type": node_type
+        })))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn empty_room_lists_all_pages_and_deletes_in_batch() {
+    let server = MockServer::start().await;
+    mount_node_1(&server, "room").await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes"))
+        .and(query_param("parent_id", "1"))
+        .and(query_param("offset", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(node_page(0, 500, 502)))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes"))
+        .and(query_param("parent_id", "1"))
+        .and(query_param("offset", "500"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(node_page(500, 2, 502)))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v4/nodes"))
+        .and(body_json(json!({ "nodeIds": [500, 501] })))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v4/nodes"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(5)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    assert_eq!(client.empty_room(1).await.unwrap(), 502);
+}
+
+#[tokio::test]
+async fn empty_room_continues_after_failed_nodes() {
+    let server = MockServer::start().await;
+    mount_node_1(&server, "room").await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(node_page(10, 2, 2)))
+        .mount(&server)
+        .await;
+
+    let forbidden = ResponseTemplate::new(403).set_body_json(json!({
+        "code": 403,
+        "message": "Forbidden"
+    }));
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v4/nodes"))
+        .respond_with(forbidden.clone())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v4/nodes/10"))
+        .respond_with(forbidden)
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v4/nodes/11"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    let results = client.empty_room_each(1).await.unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert!(matches!(
+        results[0],
+        (10, Err(DRACOONClientError::Forbidden(_)))
+    ));
+    assert!(matches!(results[1], (11, Ok(()))));
+}
+
+#[tokio::test]
+async fn empty_room_previews_children_in_dry_run() {
+    let server = MockServer::start().await;
+    mount_node_1(&server, "room").await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(node_page(10, 2, 2)))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = common::connect(
+        &server,
+        common::builder(&server).with_dry_run(true).build().unwrap(),
+    )
+    .await;
+
+    match client.empty_room(1).await {
+        Err(DRACOONClientError::DryRun(DryRunAction::EmptyRoom { room_id, node_ids })) => {
+            assert_eq!(room_id, 1);
+            assert_eq!(node_ids, vec![10, 11]);
+        }
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
+
+#[tokio::test]
+async fn empty_room_rejects_other_node_types() {
+    let server = MockServer::start().await;
+    mount_node_1(&server, "folder").await;
+
+    let client = common::connected_client(&server).await;
+
+    assert!(matches!(
+        client.empty_room(1).await,
+        Err(DRACOONClientError::MissingArguments)
+    ));
+}
+
+#[tokio::test]
+async fn ensure_folder_finds_conflicting_folder_beyond_first_page() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/nodes/folders"))
+        .respond_with(ResponseTemplate::new(409).set_body_json(json!({
+            "code": 409,
+            "message": "Node already exists"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes"))
+        .and(query_param("parent_id", "1"))
+        .and(query_param("filter", "name:eq:Reports"))
+        .and(query_param("offset", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(node_page(0, 500, 501)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes"))
+        .and(query_param("parent_id", "1"))
+        .and(query_param("filter", "name:eq:Reports"))
+        .and(query_param("offset", "500"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [{ "id": 500, "name": "Reports", "type": "folder", "parentId": 1 }],
+            "range": { "offset": 500, "limit": 500, "total": 501 }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    let folder = client.ensure_folder(1, "Reports").await.unwrap();
+
+    assert_eq!(folder.id, 500);
+    assert_eq!(folder.name, "Reports");
+}
+
+#[tokio::test]
+async fn dry_run_skips_overwriting_copies_only() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/nodes/2/copy_to"))
+        .and(body_json(json!({
+            "items": [{ "id": 3 }],
+            "resolutionStrategy": "autorename"
+        })))
+        .respond_with(
+            ResponseTemplate::new(201)
+                .set_body_json(json!({ "id": 2, "name": "Folder", "type": "folder" })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/nodes/2/copy_to"))
+        .and(body_json(json!({
+            "items": [{ "id": 3 }],
+            "resolutionStrategy": "overwrite"
+        })))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+    client.set_dry_run(true);
+
+    client
+        .copy_nodes(2, &[3], ResolutionStrategy::AutoRename)
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        client
+            .copy_nodes(2, &[3], ResolutionStrategy::Overwrite)
+            .await,
+        Err(DRACOONClientError::DryRun(DryRunAction::OverwriteCopy {
+            target_parent_id: 2,
+            ..
+        }))
+    ));
+}
+
+#[tokio::test]
+async fn room_policies_are_checked_against_server_version() {
+    let server = MockServer::start().await;
+    common::mount_software_version(&server, "4.42.2").await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/rooms/1/policies"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "defaultExpirationPeriod": 3600,
+            "isVirusProtectionEnabled": true
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    let policies = client.get_room_policies(1).await.unwrap();
+
+    assert_eq!(policies.default_expiration_period, Some(3600));
+}
+
+#[tokio::test]
+async fn room_policies_are_unavailable_on_old_servers() {
+    let server = MockServer::start().await;
+    common::mount_software_version(&server, "4.31.0").await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/nodes/rooms/1/policies"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    let req = UpdateRoomPoliciesRequest {
+        is_virus_protection_enabled: Some(true),
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        client.update_room_policies(1, &req).await,
+        Err(DRACOONClientError::FeatureUnavailable { .. })
+    ));
+}
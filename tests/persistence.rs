@@ -0,0 +1,77 @@
+#![cfg(feature = "persistence")]
+
+use dracoon_oxide::core::{DRACOONClient, DRACOONClientError};
+use dracoon_oxide::persistence::ClientState;
+use std::time::Duration;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+#[tokio::test]
+async fn client_secret_is_only_exported_on_request() {
+    let server = MockServer::start().await;
+    let client = common::builder(&server).build().unwrap();
+
+    let state = client.export_state(false);
+    let json = serde_json::to_string(&state).unwrap();
+
+    assert!(state.client_secret.is_none());
+    assert!(!json.contains(common::CLIENT_SECRET));
+
+    let state = client.export_state(true);
+
+    assert_eq!(state.client_secret.as_deref(), Some(common::CLIENT_SECRET));
+    assert!(!format!("{:?}", state).contains(common::CLIENT_SECRET));
+}
+
+#[tokio::test]
+async fn exported_state_restores_settings_and_session() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/user/ping"))
+        .and(header(
+            "Authorization",
+            format!("Bearer {}", common::ACCESS_TOKEN).as_str(),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_string("test"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::connect(
+        &server,
+        common::builder(&server)
+            .with_max_retries(1)
+            .with_timeout(Duration::from_millis(1500))
+            .with_scopes(vec!["all".to_string()])
+            .build()
+            .unwrap(),
+    )
+    .await;
+
+    let json = serde_json::to_string(&client.export_state(false)).unwrap();
+    let state: ClientState = serde_json::from_str(&json).unwrap();
+
+    let restored =
+        DRACOONClient::from_state(state, Some(common::CLIENT_SECRET.to_string())).unwrap();
+
+    assert_eq!(restored.config(), client.config());
+    assert!(restored.check_access_token_validity().unwrap());
+    assert!(restored.test_connection().await.unwrap());
+}
+
+#[tokio::test]
+async fn from_state_requires_client_secret() {
+    let server = MockServer::start().await;
+    let state = common::builder(&server)
+        .build()
+        .unwrap()
+        .export_state(false);
+
+    assert!(matches!(
+        DRACOONClient::from_state(state, None),
+        Err(DRACOONClientError::MissingArguments)
+    ));
+}
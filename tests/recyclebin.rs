@@ -0,0 +1,89 @@
+use dracoon_oxide::core::{DRACOONClientError, DryRunAction};
+use dracoon_oxide::uploads::ResolutionStrategy;
+use serde_json::json;
+use wiremock::matchers::{body_json, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+#[tokio::test]
+async fn node_versions_are_listed_by_parent_name_and_type() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/5"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 5,
+            "name": "report.pdf",
+            "type": "file",
+            "parentId": 2
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/2/deleted_nodes/versions"))
+        .and(query_param("type", "file"))
+        .and(query_param("name", "report.pdf"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "range": { "offset": 0, "limit": 500, "total": 1 },
+            "items": [{
+                "id": 42,
+                "parentId": 2,
+                "type": "file",
+                "name": "report.pdf",
+                "size": 1024,
+                "deletedAt": "2024-01-01T12:00:00Z"
+            }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+    let versions = client.get_node_versions(5, None, None).await.unwrap();
+
+    assert_eq!(versions.items.len(), 1);
+    assert_eq!(versions.items[0].id, 42);
+}
+
+#[tokio::test]
+async fn restore_deleted_nodes_sends_ids_and_strategy() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/nodes/deleted_nodes/actions/restore"))
+        .and(body_json(json!({
+            "deletedNodeIds": [42],
+            "resolutionStrategy": "autorename"
+        })))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    client
+        .restore_deleted_nodes(&[42], None, ResolutionStrategy::AutoRename)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn restore_with_overwrite_is_skipped_in_dry_run() {
+    let server = MockServer::start().await;
+    let client = common::builder(&server).with_dry_run(true).build().unwrap();
+    let client = common::connect(&server, client).await;
+
+    let res = client
+        .restore_deleted_nodes(&[42], Some(2), ResolutionStrategy::Overwrite)
+        .await;
+
+    assert!(matches!(
+        res,
+        Err(DRACOONClientError::DryRun(
+            DryRunAction::RestoreNodes { .. }
+        ))
+    ));
+}
@@ -0,0 +1,309 @@
+use chrono::{Duration, Utc};
+use dracoon_oxide::core::{DRACOONClient, DRACOONClientError, SerializedConnection};
+use serde_json::json;
+use wiremock::matchers::{body_string_contains, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn expired_connection() -> SerializedConnection {
+    let connected_at = Utc::now() - Duration::hours(10);
+
+    SerializedConnection {
+        access_token: "old-access-token".to_string(),
+        refresh_token: "refresh-token".to_string(),
+        connected_at,
+        last_activity: connected_at,
+        expires_in: 28800,
+        expires_in_inactive: 3600,
+        scopes: vec!["all".to_string()],
+    }
+}
+
+#[tokio::test]
+async fn resume_session_refreshes_expired_access_token() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .and(body_string_contains("grant_type=refresh_token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "access_token": "new-access-token",
+            "refresh_token": "new-refresh-token",
+            "token_type": "bearer",
+            "expires_in": 28800,
+            "expires_in_inactive": 3600,
+            "scope": "all"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = DRACOONClient::resume_session(
+        &server.uri(),
+        "client-id".to_string(),
+        "client-secret".to_string(),
+        expired_connection(),
+    )
+    .await
+    .unwrap();
+
+    assert!(client.check_access_token_validity().unwrap());
+    assert_eq!(
+        client.export_connection().unwrap().access_token,
+        "new-access-token"
+    );
+}
+
+#[tokio::test]
+async fn resume_session_with_rejected_refresh_token_requires_reauth() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "error": "invalid_grant",
+            "error_description": "Invalid refresh token"
+        })))
+        .mount(&server)
+        .await;
+
+    let res = DRACOONClient::resume_session(
+        &server.uri(),
+        "client-id".to_string(),
+        "client-secret".to_string(),
+        expired_connection(),
+    )
+    .await;
+
+    assert!(matches!(res, Err(DRACOONClientError::ReauthRequired)));
+}
+
+#[test]
+fn serialized_connection_masks_tokens_in_debug_output() {
+    let debug = format!("{:?}", expired_connection());
+
+    assert!(!debug.contains("old-access-token"));
+    assert!(!debug.contains("refresh-token"));
+}
+
+#[tokio::test]
+async fn rejected_access_token_is_refreshed_once_and_request_retried() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .and(body_string_contains("grant_type=refresh_token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "access_token": "new-access-token",
+            "refresh_token": "new-refresh-token",
+            "token_type": "bearer",
+            "expires_in": 28800,
+            "expires_in_inactive": 3600,
+            "scope": "all"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .and(header("authorization", "Bearer new-access-token"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({ "id": 1, "name": "Room", "type": "room" })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(json!({
+            "code": 401,
+            "message": "Unauthorized"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut client = DRACOONClient::new(
+        &server.uri(),
+        "client-id".to_string(),
+        "client-secret".to_string(),
+    )
+    .unwrap();
+
+    let mut stored = expired_connection();
+    stored.connected_at = Utc::now();
+    stored.last_activity = Utc::now();
+    client.import_connection(stored);
+
+    assert_eq!(client.get_node(1).await.unwrap().name, "Room");
+}
+
+#[tokio::test]
+async fn failed_refresh_after_rejected_access_token_returns_original_401() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "error": "invalid_grant"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(json!({
+            "code": 401,
+            "message": "Unauthorized"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut client = DRACOONClient::new(
+        &server.uri(),
+        "client-id".to_string(),
+        "client-secret".to_string(),
+    )
+    .unwrap();
+
+    let mut stored = expired_connection();
+    stored.connected_at = Utc::now();
+    stored.last_activity = Utc::now();
+    client.import_connection(stored);
+
+    let res = client.get_node(1).await;
+
+    assert!(matches!(res, Err(DRACOONClientError::DRACOONErrror(err)) if err.code == Some(401)));
+}
+
+#[tokio::test]
+async fn with_valid_token_refreshes_expired_token_before_the_call() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .and(body_string_contains("grant_type=refresh_token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "access_token": "new-access-token",
+            "refresh_token": "new-refresh-token",
+            "token_type": "bearer",
+            "expires_in": 28800,
+            "expires_in_inactive": 3600,
+            "scope": "all"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .and(header("Authorization", "Bearer new-access-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 1,
+            "name": "room",
+            "type": "room"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut client = DRACOONClient::new(
+        &server.uri(),
+        "client-id".to_string(),
+        "client-secret".to_string(),
+    )
+    .unwrap();
+    client.import_connection(expired_connection());
+
+    let node = client
+        .with_valid_token(|client| client.get_node(1))
+        .await
+        .unwrap();
+
+    assert_eq!(node.id, 1);
+    assert!(client.check_access_token_validity().unwrap());
+}
+
+#[tokio::test]
+async fn keepalive_uses_access_token_refreshed_between_ticks() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .and(body_string_contains("grant_type=refresh_token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "access_token": "new-access-token",
+            "refresh_token": "new-refresh-token",
+            "token_type": "bearer",
+            "expires_in": 28800,
+            "expires_in_inactive": 3600,
+            "scope": "all"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/user/ping"))
+        .and(header("authorization", "Bearer new-access-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("test"))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .and(header("authorization", "Bearer new-access-token"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({ "id": 1, "name": "Room", "type": "room" })),
+        )
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(json!({
+            "code": 401,
+            "message": "Unauthorized"
+        })))
+        .mount(&server)
+        .await;
+
+    let mut client = DRACOONClient::new(
+        &server.uri(),
+        "client-id".to_string(),
+        "client-secret".to_string(),
+    )
+    .unwrap();
+
+    let mut stored = expired_connection();
+    stored.connected_at = Utc::now();
+    stored.last_activity = Utc::now();
+    client.import_connection(stored);
+
+    let keepalive = client
+        .start_keepalive(std::time::Duration::from_millis(100))
+        .unwrap();
+
+    // the access token is rejected and refreshed after the keep-alive was started
+    assert_eq!(client.get_node(1).await.unwrap().name, "Room");
+
+    tokio::time::sleep(std::time::Duration::from_millis(350)).await;
+    drop(keepalive);
+
+    let pings: Vec<_> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|req| req.url.path() == "/api/v4/user/ping")
+        .collect();
+
+    assert!(pings.len() >= 2);
+    assert!(pings
+        .iter()
+        .all(|req| req.headers["authorization"] == "Bearer new-access-token"));
+}
@@ -0,0 +1,172 @@
+use dracoon_oxide::core::{DRACOONClient, DRACOONClientError};
+use serde_json::json;
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+#[tokio::test]
+async fn invalid_share_email_recipient_is_rejected() {
+    let client = DRACOONClient::new(
+        "https://dracoon.example.com/",
+        "client-id".to_string(),
+        "client-secret".to_string(),
+    )
+    .unwrap();
+
+    let recipients = vec!["jane@example.com".to_string(), "not-an-email".to_string()];
+    let res = client.send_share_email(1, &recipients, "hello").await;
+
+    assert!(matches!(res, Err(DRACOONClientError::InvalidEmail(email)) if email == "not-an-email"));
+}
+
+#[tokio::test]
+async fn download_share_reports_remaining_downloads() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/shares/downloads/7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 7,
+            "name": "share",
+            "nodeId": 1,
+            "accessKey": "access-key",
+            "cntDownloads": 3,
+            "maxDownloads": 5
+        })))
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+    let share = client.get_download_share(7).await.unwrap();
+
+    assert_eq!(share.cnt_downloads, Some(3));
+    assert_eq!(share.remaining_downloads(), Some(2));
+}
+
+#[tokio::test]
+async fn sms_protected_share_is_downloaded_with_code() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/public/shares/downloads/access-key/sms"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/public/shares/downloads/access-key"))
+        .and(body_json(json!({ "password": "123456" })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "downloadUrl": format!("{}/downloads/token", server.uri())
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/downloads/token"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("hello"))
+        .mount(&server)
+        .await;
+
+    let client = common::builder(&server).build().unwrap();
+
+    let challenge = client.request_share_sms("access-key").await.unwrap();
+
+    let mut content = Vec::new();
+    let written = client
+        .download_public_share_with_code(&challenge, "123456", &mut content)
+        .await
+        .unwrap();
+
+    assert_eq!(written, 5);
+    assert_eq!(content, b"hello");
+}
+
+#[tokio::test]
+async fn encrypted_download_share_never_sends_share_password() {
+    use dracoon_oxide::crypto::{FileKey, UserKeyPairContainer};
+    use dracoon_oxide::shares::{CreateDownloadShareRequest, ShareEncryption};
+    use pkcs8::DecodePublicKey;
+    use rsa::{Oaep, RsaPublicKey};
+
+    let server = MockServer::start().await;
+
+    let keypair = UserKeyPairContainer::generate("user-password").unwrap();
+    let public_key =
+        RsaPublicKey::from_public_key_pem(&keypair.public_key_container.public_key).unwrap();
+    let file_key = FileKey {
+        key: base64::encode(
+            public_key
+                .encrypt(
+                    &mut rand::rngs::OsRng,
+                    Oaep::new::<sha2::Sha256>(),
+                    &[7; 32],
+                )
+                .unwrap(),
+        ),
+        iv: "aXYtc2FtcGxl".to_string(),
+        tag: Some("dGFnLXNhbXBsZQ==".to_string()),
+        version: "RSA-4096/AES-256-GCM".to_string(),
+    };
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 1, "name": "secret.pdf", "type": "file", "isEncrypted": true
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/files/1/user_file_key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&file_key))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/shares/downloads"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "id": 7, "name": "secret.pdf", "nodeId": 1, "accessKey": "abc"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    let req = CreateDownloadShareRequest {
+        node_id: 1,
+        name: None,
+        password: Some("share-password".to_string()),
+        expiration: None,
+        key_pair: None,
+        file_key: None,
+        text_message_recipients: None,
+        receiver_language: None,
+    };
+    let encryption = ShareEncryption {
+        keypair: &keypair,
+        password: "user-password",
+    };
+
+    assert!(!format!("{:?}", encryption).contains("user-password"));
+
+    client
+        .create_download_share(&req, Some(encryption))
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let share_request = requests
+        .iter()
+        .find(|req| req.url.path() == "/api/v4/shares/downloads")
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&share_request.body).unwrap();
+
+    assert!(body.get("password").is_none());
+    assert!(body.get("keyPair").is_some());
+    assert!(body.get("fileKey").is_some());
+}
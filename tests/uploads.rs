@@ -0,0 +1,420 @@
+use dracoon_oxide::core::DRACOONClientError;
+use dracoon_oxide::crypto::{FileEncryptor, FileKey, UserKeyPairContainer};
+use dracoon_oxide::uploads::{detect_media_type, UploadOptions, DEFAULT_MEDIA_TYPE};
+use serde_json::json;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+/// mounts an unencrypted parent room, an upload channel with a chunk url on the mock
+/// server and the finalization
+async fn mount_upload(server: &MockServer) {
+    mount_parent(server, false).await;
+    mount_channel(server).await;
+}
+
+/// mounts an upload channel with a chunk url and the finalization
+async fn mount_channel(server: &MockServer) {
+    Mock::given(method("POST"))
+        .and(path("/api/v4/nodes/files/uploads"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "uploadId": "upload-id",
+            "uploadUrl": format!("{}/api/v4/uploads/token", server.uri()),
+            "token": "token"
+        })))
+        .mount(server)
+        .await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/nodes/files/uploads/upload-id"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "id": 2,
+            "name": "file.txt",
+            "type": "file"
+        })))
+        .mount(server)
+        .await;
+}
+
+/// mounts the parent room (node 1)
+async fn mount_parent(server: &MockServer, is_encrypted: bool) {
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 1,
+            "name": "Room",
+            "type": "room",
+            "isEncrypted": is_encrypted
+        })))
+        .mount(server)
+        .await;
+}
+
+fn options() -> UploadOptions {
+    UploadOptions {
+        check_size: false,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn upload_chunks_send_no_expect_header() {
+    let server = MockServer::start().await;
+    mount_upload(&server).await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/uploads/token"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    client
+        .upload_bytes(1, "file.txt", b"content", options())
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let chunk = requests
+        .iter()
+        .find(|req| req.url.path() == "/api/v4/uploads/token")
+        .unwrap();
+
+    assert!(!chunk.headers.contains_key("expect"));
+}
+
+#[tokio::test]
+async fn pipelined_upload_sends_chunks_in_order() {
+    let server = MockServer::start().await;
+    mount_upload(&server).await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/uploads/token"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    let options = UploadOptions {
+        chunk_size: 4,
+        pipeline_depth: 1,
+        ..options()
+    };
+
+    client
+        .upload_bytes(1, "file.txt", b"0123456789", options)
+        .await
+        .unwrap();
+
+    let ranges: Vec<String> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|req| req.url.path() == "/api/v4/uploads/token")
+        .map(|req| req.headers["content-range"].to_str().unwrap().to_string())
+        .collect();
+
+    assert_eq!(ranges, vec!["bytes 0-3/10", "bytes 4-7/10", "bytes 8-9/10"]);
+}
+
+#[tokio::test]
+async fn pipelined_upload_stops_on_chunk_error() {
+    let server = MockServer::start().await;
+    mount_upload(&server).await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/uploads/token"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "code": 400,
+            "message": "Bad request"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v4/nodes/files/uploads/upload-id"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::builder(&server)
+        .with_max_retries(0)
+        .build()
+        .unwrap();
+    let client = common::connect(&server, client).await;
+
+    let options = UploadOptions {
+        chunk_size: 4,
+        ..options()
+    };
+
+    let res = client
+        .upload_bytes(1, "file.txt", b"0123456789", options)
+        .await;
+
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn upload_returns_timeout_and_removes_channel() {
+    let server = MockServer::start().await;
+    mount_upload(&server).await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/uploads/token"))
+        .respond_with(ResponseTemplate::new(201).set_delay(Duration::from_secs(5)))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v4/nodes/files/uploads/upload-id"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    let options = UploadOptions {
+        timeout: Some(Duration::from_millis(200)),
+        ..options()
+    };
+
+    let res = client
+        .upload_bytes(1, "file.txt", b"0123456789", options)
+        .await;
+
+    assert!(matches!(res, Err(DRACOONClientError::Timeout)));
+}
+
+#[test]
+fn media_type_is_detected_from_extension() {
+    assert_eq!(detect_media_type("report.PDF"), "application/pdf");
+    assert_eq!(detect_media_type("photo.final.jpg"), "image/jpeg");
+    assert_eq!(detect_media_type("archive.unknown"), DEFAULT_MEDIA_TYPE);
+    assert_eq!(detect_media_type("README"), DEFAULT_MEDIA_TYPE);
+    assert_eq!(detect_media_type(".txt"), DEFAULT_MEDIA_TYPE);
+}
+
+/// media type sent with the upload channel request of an upload
+async fn uploaded_media_type(options: UploadOptions) -> Option<String> {
+    let server = MockServer::start().await;
+    mount_upload(&server).await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/uploads/token"))
+        .respond_with(ResponseTemplate::new(201))
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    client
+        .upload_bytes(1, "image.png", b"0123456789", options)
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let channel_req = requests
+        .iter()
+        .find(|req| req.url.path() == "/api/v4/nodes/files/uploads")
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&channel_req.body).unwrap();
+
+    body["mediaType"].as_str().map(String::from)
+}
+
+#[tokio::test]
+async fn upload_sends_detected_or_given_media_type() {
+    assert_eq!(
+        uploaded_media_type(options()).await.as_deref(),
+        Some("image/png")
+    );
+
+    let given = UploadOptions {
+        media_type: Some("image/x-custom".to_string()),
+        ..options()
+    };
+    assert_eq!(
+        uploaded_media_type(given).await.as_deref(),
+        Some("image/x-custom")
+    );
+
+    let disabled = UploadOptions {
+        detect_media_type: false,
+        ..options()
+    };
+    assert_eq!(uploaded_media_type(disabled).await, None);
+}
+
+#[tokio::test]
+async fn cleanup_stale_uploads_cancels_old_channels() {
+    let server = MockServer::start().await;
+    let now = chrono::Utc::now();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/nodes/files/uploads"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "range": { "offset": 0, "limit": 500, "total": 5 },
+            "items": [
+                { "uploadId": "failing", "name": "d.txt", "createdAt": now - chrono::Duration::days(3) },
+                { "uploadId": "gone", "name": "e.txt", "createdAt": now - chrono::Duration::days(3) },
+                { "uploadId": "stale", "name": "a.txt", "createdAt": now - chrono::Duration::days(2) },
+                { "uploadId": "recent", "name": "b.txt", "createdAt": now - chrono::Duration::minutes(5) },
+                { "uploadId": "unknown", "name": "c.txt" }
+            ]
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v4/nodes/files/uploads/stale"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v4/nodes/files/uploads/failing"))
+        .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+            "code": 403,
+            "message": "Forbidden"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v4/nodes/files/uploads/gone"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "code": 404,
+            "message": "Upload not found"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    let cleanup = client
+        .cleanup_stale_uploads(Duration::from_secs(24 * 60 * 60))
+        .await
+        .unwrap();
+
+    // the failing channel does not stop the cleanup, the missing one is already gone
+    assert_eq!(cleanup.cancelled, 2);
+    assert_eq!(cleanup.failed.len(), 1);
+    assert_eq!(cleanup.failed[0].0, "failing");
+}
+
+#[tokio::test]
+async fn upload_into_encrypted_room_encrypts_chunks_and_sends_file_key() {
+    let server = MockServer::start().await;
+    mount_parent(&server, true).await;
+    mount_channel(&server).await;
+
+    let keypair = UserKeyPairContainer::generate("user-password").unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/user/account/keypair"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&keypair))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/uploads/token"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    let options = UploadOptions {
+        chunk_size: 4,
+        ..options()
+    };
+
+    client
+        .upload_string(1, "file.txt", "secret text", options)
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+
+    let encrypted: Vec<u8> = requests
+        .iter()
+        .filter(|req| req.url.path() == "/api/v4/uploads/token")
+        .flat_map(|req| req.body.clone())
+        .collect();
+
+    let complete: serde_json::Value = requests
+        .iter()
+        .find(|req| req.url.path() == "/api/v4/nodes/files/uploads/upload-id")
+        .map(|req| serde_json::from_slice(&req.body).unwrap())
+        .unwrap();
+    let file_key: FileKey = serde_json::from_value(complete["fileKey"].clone()).unwrap();
+
+    assert_eq!(file_key.version, "RSA-4096/AES-256-GCM");
+
+    // the file key decrypted by the user encrypts the plain text to the uploaded chunks
+    let plain_file_key = keypair
+        .decrypt("user-password")
+        .unwrap()
+        .decrypt_file_key(&file_key)
+        .unwrap();
+    let mut encryptor = FileEncryptor::new(&plain_file_key).unwrap();
+
+    assert_ne!(encrypted, b"secret text");
+    assert_eq!(encrypted, encryptor.encrypt(b"secret text"));
+    assert_eq!(file_key.tag, Some(encryptor.finalize()));
+}
+
+#[tokio::test]
+async fn chunk_uploads_wait_for_concurrency_limiter() {
+    let server = MockServer::start().await;
+    mount_upload(&server).await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v4/uploads/token"))
+        .respond_with(ResponseTemplate::new(201).set_delay(Duration::from_millis(300)))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let client = common::builder(&server)
+        .with_max_concurrent_requests(2)
+        .build()
+        .unwrap();
+    let client = common::connect(&server, client).await;
+
+    let started = std::time::Instant::now();
+
+    let (first, second, third, in_flight) = futures::join!(
+        client.upload_bytes(1, "file.txt", b"first", options()),
+        client.upload_bytes(1, "file.txt", b"second", options()),
+        client.upload_bytes(1, "file.txt", b"third", options()),
+        async {
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            client.in_flight_requests()
+        }
+    );
+
+    first.unwrap();
+    second.unwrap();
+    third.unwrap();
+
+    // two chunk PUTs in flight, the third one waits for a free slot
+    assert_eq!(in_flight, 2);
+    assert!(started.elapsed() >= Duration::from_millis(600));
+}
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use dracoon_oxide::users::UpdateUserRequest;
+use serde_json::json;
+
+#[test]
+fn lock_user_request_only_contains_lock_flag() {
+    let req = UpdateUserRequest::lock(true);
+
+    assert_eq!(
+        serde_json::to_value(&req).unwrap(),
+        json!({ "isLocked": true })
+    );
+
+    let req = UpdateUserRequest::lock(false);
+
+    assert_eq!(
+        serde_json::to_value(&req).unwrap(),
+        json!({ "isLocked": false })
+    );
+}
+
+#[test]
+fn user_expiration_request_contains_expiration_date() {
+    let expire_at: DateTime<Utc> = "2022-01-31T12:00:00Z".parse().unwrap();
+    let req = UpdateUserRequest::expiration(Some(expire_at));
+
+    assert_eq!(
+        serde_json::to_value(&req).unwrap(),
+        json!({
+            "expiration": {
+                "enableExpiration": true,
+                "expireAt": "2022-01-31T12:00:00Z"
+            }
+        })
+    );
+}
+
+#[test]
+fn user_expiration_request_without_date_disables_expiration() {
+    let req = UpdateUserRequest::expiration(None);
+
+    assert_eq!(
+        serde_json::to_value(&req).unwrap(),
+        json!({ "expiration": { "enableExpiration": false } })
+    );
+}
@@ -0,0 +1,66 @@
+use dracoon_oxide::config::ApiVersion;
+use dracoon_oxide::core::DRACOONClientError;
+use dracoon_oxide::webhooks::{Webhook, WebhookConfig};
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+#[test]
+fn webhook_secret_is_masked_in_debug_output() {
+    let webhook: Webhook = serde_json::from_value(json!({
+        "id": 1,
+        "name": "siem",
+        "url": "https://hooks.example.com/dracoon",
+        "secret": "super-secret",
+        "isEnabled": true,
+        "eventTypeNames": ["file.created"]
+    }))
+    .unwrap();
+
+    let debug = format!("{:?}", webhook);
+
+    assert!(!debug.contains("super-secret"));
+    assert!(debug.contains("***"));
+}
+
+#[test]
+fn webhook_config_serializes_secret_but_masks_it_in_debug_output() {
+    let config = WebhookConfig {
+        name: "siem".to_string(),
+        url: "https://hooks.example.com/dracoon".to_string(),
+        event_type_names: vec!["file.created".to_string()],
+        secret: Some("super-secret".to_string()),
+        is_enabled: Some(true),
+    };
+
+    assert_eq!(
+        serde_json::to_value(&config).unwrap()["secret"],
+        json!("super-secret")
+    );
+    assert!(!format!("{:?}", config).contains("super-secret"));
+}
+
+#[tokio::test]
+async fn webhooks_are_unavailable_on_old_servers() {
+    let server = MockServer::start().await;
+    common::mount_software_version(&server, "4.18.3").await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/settings/webhooks"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = common::connected_client(&server).await;
+
+    assert!(matches!(
+        client.get_webhooks(None, None, None).await,
+        Err(DRACOONClientError::FeatureUnavailable { required, .. })
+            if required == ApiVersion::new(4, 19, 0)
+    ));
+
+    client.close().await.unwrap();
+}